@@ -0,0 +1,55 @@
+//! Times the byte-level day 1 on a generated document of 1 GiB, or of
+//! `DAY_01_BENCH_BYTES` bytes if set.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+#[path = "../src/bytes.rs"]
+mod bytes;
+
+const TOKENS: [&str; 16] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "1", "5", "9", "x",
+    "q", "tw", "eigh",
+];
+
+// lines of 8 to 15 tokens picked by a xorshift, so every run sees the same document
+fn document(size: usize) -> Vec<u8> {
+    let mut document = Vec::with_capacity(size + 64);
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    while document.len() < size {
+        for _ in 0..8 + next() % 8 {
+            document.extend_from_slice(TOKENS[(next() % 16) as usize].as_bytes());
+        }
+        document.push(b'\n');
+    }
+
+    document
+}
+
+fn bench(c: &mut Criterion) {
+    let size = std::env::var("DAY_01_BENCH_BYTES")
+        .map(|size| size.parse().expect("DAY_01_BENCH_BYTES should be a number"))
+        .unwrap_or(1 << 30);
+    let document = document(size);
+
+    let mut group = c.benchmark_group("day 1 bytes");
+    group
+        .sample_size(10)
+        .throughput(Throughput::Bytes(document.len() as u64));
+    group.bench_function("task 1", |b| {
+        b.iter(|| bytes::calibration_sum(&document, false))
+    });
+    group.bench_function("task 2", |b| {
+        b.iter(|| bytes::calibration_sum(&document, true))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
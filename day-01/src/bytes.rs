@@ -0,0 +1,42 @@
+//! Day 1 over raw bytes for documents too large to scan with the
+//! vocabulary aware `Scanner`. Every line is searched from the front for its
+//! first digit and from the back for its last one, without allocating. Only
+//! the English words are known.
+
+const WORDS: [&[u8]; 9] = [
+    b"one", b"two", b"three", b"four", b"five", b"six", b"seven", b"eight", b"nine",
+];
+
+// the digit written or, with `words`, spelled out at the start of `bytes`
+fn digit_at(bytes: &[u8], words: bool) -> Option<u32> {
+    match bytes[0] {
+        b @ b'1'..=b'9' => Some(u32::from(b - b'0')),
+        _ if words => WORDS
+            .iter()
+            .position(|word| bytes.starts_with(word))
+            .map(|i| i as u32 + 1),
+        _ => None,
+    }
+}
+
+/// The calibration value of one line, 0 if it holds no digit.
+pub fn calibration_value(line: &[u8], words: bool) -> u32 {
+    let first = (0..line.len()).find_map(|i| digit_at(&line[i..], words));
+    let last = (0..line.len())
+        .rev()
+        .find_map(|i| digit_at(&line[i..], words));
+
+    match (first, last) {
+        (Some(first), Some(last)) => 10 * first + last,
+        _ => 0,
+    }
+}
+
+/// The sum of the calibration values of all lines in `document`, reading
+/// spelled out digits too if `words` is set.
+pub fn calibration_sum(document: &[u8], words: bool) -> u64 {
+    document
+        .split(|b| *b == b'\n')
+        .map(|line| u64::from(calibration_value(line, words)))
+        .sum()
+}
@@ -1,103 +1,325 @@
-use std::collections::HashMap;
+use std::fmt::Display;
 
-fn task_1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|l| {
-            let numbers = ['1', '2', '3', '4', '5', '6', '7', '8', '9'];
-            let mut res: Vec<_> = l.match_indices(numbers).collect();
+use aho_corasick::AhoCorasick;
+use shared::{
+    explain::Explained,
+    input::normalize,
+    variants::{self, Variant},
+};
+use vocabulary::Vocabulary;
+
+mod bytes;
+mod vocabulary;
+
+/// A number, written or spelled out, and the byte offset it starts at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Token {
+    pos: usize,
+    value: u32,
+}
+
+impl Token {
+    fn first_digit(self) -> u32 {
+        let mut value = self.value;
+        while value >= 10 {
+            value /= 10;
+        }
+        value
+    }
+
+    fn last_digit(self) -> u32 {
+        self.value % 10
+    }
+}
+
+/// Finds the tokens of a vocabulary in a line, overlapping ones included,
+/// so "eightwo" holds both an 8 and a 2.
+struct Scanner {
+    matcher: AhoCorasick,
+    values: Vec<u32>,
+}
+
+impl Scanner {
+    fn new(vocabulary: &Vocabulary) -> Self {
+        let words = vocabulary.0.iter().map(|(word, _)| word);
+
+        Scanner {
+            matcher: AhoCorasick::new(words).unwrap(),
+            values: vocabulary.0.iter().map(|(_, value)| *value).collect(),
+        }
+    }
+
+    // the first and last token of `line` in one pass
+    fn first_last(&self, line: &str) -> Option<(Token, Token)> {
+        self.matcher
+            .find_overlapping_iter(line)
+            .map(|m| Token {
+                pos: m.start(),
+                value: self.values[m.pattern().as_usize()],
+            })
+            .fold(None, |found, token| match found {
+                None => Some((token, token)),
+                Some((first, last)) => Some((
+                    if token.pos < first.pos { token } else { first },
+                    if token.pos > last.pos { token } else { last },
+                )),
+            })
+    }
+}
 
-            res.sort_by(|a,b| a.0.cmp(&b.0));
+/// What one line of a calibration document is worth, and the first and
+/// last number it was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Record {
+    line: usize,
+    first: Token,
+    last: Token,
+    value: u32,
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: {} at {}, {} at {} -> {}",
+            self.line, self.first.value, self.first.pos, self.last.value, self.last.pos, self.value
+        )
+    }
+}
+
+// one record per line, or the number of a line without any number in it
+fn records<'a>(
+    input: &'a str,
+    scanner: &'a Scanner,
+) -> impl Iterator<Item = Result<Record, usize>> + 'a {
+    input.lines().enumerate().map(|(i, l)| {
+        let (first, last) = scanner.first_last(l).ok_or(i + 1)?;
 
-            if res.len() > 0 {
-                let first = res.first().unwrap().1.parse::<u32>().unwrap();
-                let last = res.last().unwrap().1.parse::<u32>().unwrap();
+        Ok(Record {
+            line: i + 1,
+            first,
+            last,
+            value: 10 * first.first_digit() + last.last_digit(),
+        })
+    })
+}
 
-                10 * first + last
-            } else {
-                0
+fn solve(input: &str, scanner: &Scanner, mut steps: Option<&mut Vec<String>>) -> u32 {
+    input
+        .lines()
+        .zip(records(input, scanner))
+        .enumerate()
+        .map(|(i, (l, record))| {
+            // lines without a number are worth nothing, see `--strict`
+            let value = record.map_or(0, |r| r.value);
+            if let Some(steps) = &mut steps {
+                steps.push(format!("line {}: {l} -> {value}", i + 1));
             }
+            value
         })
         .sum::<u32>()
 }
 
-fn task_2(input: &str) -> u32 {
-    let mut nums = HashMap::new();
-    nums.insert("one", "one1one");
-    nums.insert("two", "two2two");
-    nums.insert("three", "three3three");
-    nums.insert("four", "four4four");
-    nums.insert("five", "five5five");
-    nums.insert("six", "six6six");
-    nums.insert("seven", "seven7seven");
-    nums.insert("eight", "eight8eight");
-    nums.insert("nine", "nine9nine");
-
-    let inp: String = input
-        .lines()
-        .map(|l| {
-            let mut matches: Vec<(usize, &str)> = Vec::new();
+fn scanner_1() -> Scanner {
+    Scanner::new(&Vocabulary::digits())
+}
 
-            for n in nums.iter() {
-                l.match_indices(n.0)
-                    .into_iter()
-                    .for_each(|e| matches.push(e));
-            }
+fn scanner_2(words: &Vocabulary) -> Scanner {
+    Scanner::new(&Vocabulary::digits().and(words.clone()))
+}
 
-            matches.sort_by(|a, b| a.0.cmp(&b.0));
+fn explain_1(input: &str) -> Explained<u32> {
+    Explained::record(|steps| solve(input, &scanner_1(), Some(steps)))
+}
 
-            let mut res = l.to_string();
-            if matches.len() > 0 {
-                let first = matches.first().unwrap();
-                let last = matches.last().unwrap();
+fn task_1(input: &str) -> u32 {
+    solve(input, &scanner_1(), None)
+}
 
-                res = res.replacen(first.1, nums.get(first.1).unwrap(), 1);
-                res = res.replace(last.1, nums.get(last.1).unwrap());
-            }
-            res
-        })
-        .reduce(|acc, s| acc + "\n" + &s)
-        .unwrap();
+fn explain_2(input: &str, words: &Vocabulary) -> Explained<u32> {
+    Explained::record(|steps| solve(input, &scanner_2(words), Some(steps)))
+}
+
+fn task_2(input: &str) -> u32 {
+    task_2_in(input, &Vocabulary::english())
+}
+
+// reads spelled out numbers in `words` instead of English
+fn task_2_in(input: &str, words: &Vocabulary) -> u32 {
+    solve(input, &scanner_2(words), None)
+}
+
+fn task_1_bytes(input: &str) -> u64 {
+    bytes::calibration_sum(input.as_bytes(), false)
+}
 
-    task_1(&inp)
+fn task_2_bytes(input: &str) -> u64 {
+    bytes::calibration_sum(input.as_bytes(), true)
 }
 
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let requested = Vocabulary::requested();
+    let words = requested.clone().unwrap_or_else(Vocabulary::english);
+    let solve_2 = move |input: &str| match &requested {
+        Some(words) => task_2_in(input, words),
+        None => task_2(input),
+    };
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", {
+            let solve_2 = solve_2.clone();
+            move |input| solve_2(input).to_string()
+        }),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    let scanners = [("task 1", scanner_1()), ("task 2", scanner_2(&words))];
+
+    if std::env::args().any(|arg| arg == "--records") {
+        for (label, scanner) in scanners.iter() {
+            println!("{label}:");
+            for record in records(input, scanner) {
+                match record {
+                    Ok(record) => println!("  {record}"),
+                    Err(line) => println!("  line {line}: no number"),
+                }
+            }
+        }
+        return;
+    }
+
+    if std::env::args().any(|arg| arg == "--strict") {
+        let mut ok = true;
+        for (label, scanner) in scanners.iter() {
+            for line in records(input, scanner).filter_map(Result::err) {
+                eprintln!("{label}: line {line} has no number");
+                ok = false;
+            }
+        }
+        if !ok {
+            std::process::exit(1);
+        }
+    }
+
+    if std::env::args().any(|arg| arg == "--explain") {
+        explain_1(input).print("task 1");
+        explain_2(input, &words).print("task 2");
+        return;
+    }
 
-    println!("task 1: {}", task_1(input));
-    println!("task 2: {}", task_2(input));
+    // the byte variants sum in u64 for huge documents
+    let part_1 = [
+        Variant::new("scanner", |input| u64::from(task_1(input))),
+        Variant::new("bytes", task_1_bytes),
+    ];
+    let mut part_2 = vec![Variant::new("scanner", |input| u64::from(solve_2(input)))];
+    // the byte variants only know English words
+    if words == Vocabulary::english() {
+        part_2.push(Variant::new("bytes", task_2_bytes));
+    }
+
+    if std::env::args().any(|arg| arg == "--verify") {
+        let part_1_ok = variants::verify("task 1", input, &part_1);
+        let part_2_ok = variants::verify("task 2", input, &part_2);
+        std::process::exit(if part_1_ok && part_2_ok { 0 } else { 1 });
+    }
+
+    variants::run("task 1", input, &part_1);
+    variants::run("task 2", input, &part_2);
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
+        part_1 as part_1_bytes => task_1_bytes,
+        part_2 as part_2_bytes => task_2_bytes,
+    }
+
     #[test]
-    fn task_1_works() {
-        let input = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
+    fn explain_works() {
+        let input = "two1nine
 treb7uchet";
 
-        let result = super::task_1(input);
-        assert_eq!(result, 142);
+        let result = explain_2(input, &Vocabulary::english());
+        assert_eq!(result.answer, 106);
+        assert_eq!(
+            result.steps,
+            vec!["line 1: two1nine -> 29", "line 2: treb7uchet -> 77"]
+        );
     }
 
     #[test]
-    fn task_2_works() {
-        let input = "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen";
+    fn scanner_finds_overlaps() {
+        let scanner = Scanner::new(&Vocabulary::digits().and(Vocabulary::english()));
 
-        let result = super::task_2(input);
-        assert_eq!(result, 281);
+        assert_eq!(
+            scanner.first_last("3eightwo"),
+            Some((Token { pos: 0, value: 3 }, Token { pos: 5, value: 2 }))
+        );
+        assert_eq!(scanner.first_last("abc"), None);
+    }
 
-        let input = "twone";
+    #[test]
+    fn vocabularies_work() {
+        let german = Vocabulary::builtin("german").unwrap();
+        assert_eq!(explain_2("fünfundzwanzig7drei", &german).answer, 53);
+
+        let french = Vocabulary::builtin("french").unwrap();
+        assert_eq!(explain_2("huitcentdeux", &french).answer, 82);
 
-        let result = super::task_2(input);
-        assert_eq!(result, 21);
+        let numbers = Vocabulary::parse("zehn: 10\nzwölf: 12");
+        assert_eq!(explain_2("zwölf4zehn", &numbers).answer, 10);
+    }
+
+    #[test]
+    fn records_work() {
+        let records: Vec<_> = records("a1b2\nxyz", &scanner_1()).collect();
+
+        assert_eq!(
+            records,
+            vec![
+                Ok(Record {
+                    line: 1,
+                    first: Token { pos: 1, value: 1 },
+                    last: Token { pos: 3, value: 2 },
+                    value: 12,
+                }),
+                Err(2),
+            ]
+        );
+        assert_eq!(
+            records[0].unwrap().to_string(),
+            "line 1: 1 at 1, 2 at 3 -> 12"
+        );
+    }
+
+    #[test]
+    fn bytes_calibration_value_works() {
+        for (line, digits, words) in [
+            ("a1b2c3", 13, 13),
+            ("treb7uchet", 77, 77),
+            ("eightwo", 0, 82),
+            ("7pqrstsixteen", 77, 76),
+            ("zero", 0, 0),
+            ("", 0, 0),
+        ] {
+            assert_eq!(
+                bytes::calibration_value(line.as_bytes(), false),
+                digits,
+                "{line}"
+            );
+            assert_eq!(
+                bytes::calibration_value(line.as_bytes(), true),
+                words,
+                "{line}"
+            );
+        }
     }
 }
@@ -0,0 +1,137 @@
+use std::fs;
+
+const DIGITS: [(&str, u32); 9] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+const GERMAN: [(&str, u32); 9] = [
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+const FRENCH: [(&str, u32); 9] = [
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+
+/// Words that stand for numbers, each with the number it stands for. A
+/// number with several digits, like "zwölf", counts as its first digit at
+/// the start of a line and as its last digit at the end.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Vocabulary(pub Vec<(String, u32)>);
+
+impl Vocabulary {
+    fn from_table(table: &[(&str, u32)]) -> Self {
+        Vocabulary(table.iter().map(|(w, n)| (w.to_string(), *n)).collect())
+    }
+
+    pub fn digits() -> Self {
+        Self::from_table(&DIGITS)
+    }
+
+    pub fn english() -> Self {
+        Self::from_table(&ENGLISH)
+    }
+
+    /// The built-in vocabulary called `name`: english, german or french.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "english" => Some(Self::from_table(&ENGLISH)),
+            "german" => Some(Self::from_table(&GERMAN)),
+            "french" => Some(Self::from_table(&FRENCH)),
+            _ => None,
+        }
+    }
+
+    // "zehn: 10\nelf: 11" -> [("zehn", 10), ("elf", 11)]
+    pub fn parse(text: &str) -> Self {
+        let words = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| {
+                let (word, number) = l
+                    .split_once(':')
+                    .unwrap_or_else(|| panic!("line {}: expected `word: number`", i + 1));
+                let number = number
+                    .trim()
+                    .parse()
+                    .unwrap_or_else(|e| panic!("line {}: {e}", i + 1));
+                (word.trim().to_string(), number)
+            })
+            .collect();
+
+        Vocabulary(words)
+    }
+
+    /// The built-in vocabulary called `name`, or else the one in the file
+    /// at `name`.
+    pub fn load(name: &str) -> Self {
+        Self::builtin(name).unwrap_or_else(|| {
+            let text = fs::read_to_string(name).unwrap_or_else(|e| {
+                panic!("{name} is no built-in vocabulary and could not be read: {e}")
+            });
+            Self::parse(&text)
+        })
+    }
+
+    /// The vocabulary passed with `--vocabulary`, if any.
+    pub fn requested() -> Option<Self> {
+        std::env::args()
+            .skip_while(|arg| arg != "--vocabulary")
+            .nth(1)
+            .map(|name| Self::load(&name))
+    }
+
+    pub fn and(mut self, other: Vocabulary) -> Self {
+        self.0.extend(other.0);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_works() {
+        assert_eq!(
+            Vocabulary::parse("zehn: 10\n\nzwölf : 12\n"),
+            Vocabulary(vec![("zehn".to_string(), 10), ("zwölf".to_string(), 12)])
+        );
+    }
+}
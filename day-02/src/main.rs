@@ -1,8 +1,8 @@
 use regex::Regex;
+use shared::{explain::Explained, input::normalize};
 
-fn task_1(input: &str) -> u32 {
+fn solve_1(input: &str, mut steps: Option<&mut Vec<String>>) -> u32 {
     let max_cubes = (12, 13, 14); // (R,G,B)
-
     input
         .lines()
         .map(|l| {
@@ -47,18 +47,33 @@ fn task_1(input: &str) -> u32 {
                 .all(|e| e == true);
 
             if is_possible {
+                if let Some(steps) = &mut steps {
+                    steps.push(format!("Game {game_id}: possible"));
+                }
                 game_id
             } else {
+                if let Some(steps) = &mut steps {
+                    steps.push(format!("Game {game_id}: impossible"));
+                }
                 0
             }
         })
         .sum::<u32>()
 }
 
-fn task_2(input: &str) -> u32 {
+fn explain_1(input: &str) -> Explained<u32> {
+    Explained::record(|steps| solve_1(input, Some(steps)))
+}
+
+fn task_1(input: &str) -> u32 {
+    solve_1(input, None)
+}
+
+fn solve_2(input: &str, mut steps: Option<&mut Vec<String>>) -> u32 {
     input
         .lines()
-        .map(|l| {
+        .enumerate()
+        .map(|(i, l)| {
             let mut str = l.to_string();
             str = str.split(": ").last().unwrap().to_string();
 
@@ -95,13 +110,43 @@ fn task_2(input: &str) -> u32 {
                 }
             }
 
+            if let Some(steps) = &mut steps {
+                steps.push(format!(
+                    "Game {}: {} red, {} green, {} blue -> {}",
+                    i + 1,
+                    min_cubes.0,
+                    min_cubes.1,
+                    min_cubes.2,
+                    min_cubes.0 * min_cubes.1 * min_cubes.2
+                ));
+            }
             min_cubes
         })
         .fold(0, |mut acc, e| {acc += e.0 * e.1 * e.2; acc})
 }
 
+fn explain_2(input: &str) -> Explained<u32> {
+    Explained::record(|steps| solve_2(input, Some(steps)))
+}
+
+fn task_2(input: &str) -> u32 {
+    solve_2(input, None)
+}
+
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    if std::env::args().any(|arg| arg == "--explain") {
+        explain_1(input).print("task 1");
+        explain_2(input).print("task 2");
+        return;
+    }
 
     println!("task 1: {}", task_1(input));
     println!("task 2: {}", task_2(input));
@@ -109,29 +154,25 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use crate::task_2;
+    use super::*;
 
-    #[test]
-    fn task_1_works() {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-
-        let result = super::task_1(input);
-        assert_eq!(result, 8);
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
     }
 
     #[test]
-    fn task_2_works() {
+    fn explain_works() {
         let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-
-        let result = task_2(input);
-        assert_eq!(result, 2286);
+Game 2: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
+
+        assert_eq!(
+            explain_1(input).steps,
+            vec!["Game 1: possible", "Game 2: impossible"]
+        );
+        assert_eq!(
+            explain_2(input).steps[0],
+            "Game 1: 4 red, 2 green, 6 blue -> 48"
+        );
     }
 }
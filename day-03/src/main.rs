@@ -1,101 +1,94 @@
+use std::collections::HashMap;
+
 use regex::Regex;
+use shared::{geometry::Pos, input::normalize};
 
-fn task_1(input: &str) -> u32 {
-    let mat: Vec<Vec<String>> = input.lines().map(|l| {
-        let mut chars: Vec<String> = Vec::new();
+struct Schematic {
+    numbers: Vec<u32>,
+    // the index into `numbers` of the number each digit belongs to
+    digits: HashMap<Pos, usize>,
+    symbols: Vec<(Pos, char)>,
+}
 
-        for c in l.chars() {
-            chars.push(c.to_string());
-        }
+impl Schematic {
+    // the numbers with a digit next to `p`, diagonals included, each once
+    fn numbers_around(&self, p: Pos) -> Vec<u32> {
+        let mut around: Vec<usize> = p
+            .neighbors8()
+            .iter()
+            .filter_map(|n| self.digits.get(n).copied())
+            .collect();
+        around.sort();
+        around.dedup();
+
+        around.into_iter().map(|i| self.numbers[i]).collect()
+    }
+}
+
+fn parse_schematic(input: &str) -> Schematic {
+    let re = Regex::new(r"(\d+)").unwrap();
+    let mut schematic = Schematic {
+        numbers: vec![],
+        digits: HashMap::new(),
+        symbols: vec![],
+    };
 
-        let re = Regex::new(r"(\d+)").unwrap();
+    for (y, l) in input.lines().enumerate() {
         for m in re.find_iter(l) {
-            for i in m.start()..m.end() {
-                chars[i] = m.as_str().to_string();
+            for x in m.start()..m.end() {
+                schematic
+                    .digits
+                    .insert(Pos::new(x as i32, y as i32), schematic.numbers.len());
             }
+            schematic.numbers.push(m.as_str().parse().unwrap());
         }
 
-        chars
-    })
-    .collect();
-
-    let mut total = 0;
-    for (x, col) in mat.iter().enumerate() {
-        for (y, c) in col.iter().enumerate() {
-            if c.parse::<u32>().is_ok() || *c == "." {continue};
-
-            let mut neighbors: Vec<u32> = Vec::new();
-            let mut last_elem = 0;
-            for column in mat.iter().skip(x-1).take(3) {
-                for elem in column.iter().skip(y-1).take(3) {
-                    let parsed_elem = elem.parse::<u32>();
-                    if parsed_elem.is_ok() {
-                        let num = parsed_elem.unwrap();
-                        if num != last_elem {
-                            neighbors.push(num);
-                            last_elem = num;
-                        }
-                    };
-                }
+        for (x, c) in l.chars().enumerate() {
+            if !c.is_ascii_digit() && c != '.' {
+                schematic.symbols.push((Pos::new(x as i32, y as i32), c));
             }
-            
-            total += neighbors.iter().sum::<u32>();
         }
     }
 
-    total
+    schematic
 }
 
-fn task_2(input: &str) -> u32 {
-    let mat: Vec<Vec<String>> = input.lines().map(|l| {
-        let mut chars: Vec<String> = Vec::new();
-
-        for c in l.chars() {
-            chars.push(c.to_string());
-        }
-
-        let re = Regex::new(r"(\d+)").unwrap();
-        for m in re.find_iter(l) {
-            for i in m.start()..m.end() {
-                chars[i] = m.as_str().to_string();
-            }
-        }
+fn task_1(input: &str) -> u32 {
+    let schematic = parse_schematic(input);
 
-        chars
-    })
-    .collect();
-
-    let mut total = 0;
-    for (x, col) in mat.iter().enumerate() {
-        for (y, c) in col.iter().enumerate() {
-            if *c != "*" {continue};
-
-            let mut neighbors: Vec<u32> = Vec::new();
-            let mut last_elem = 0;
-            for column in mat.iter().skip(x-1).take(3) {
-                for elem in column.iter().skip(y-1).take(3) {
-                    let parsed_elem = elem.parse::<u32>();
-                    if parsed_elem.is_ok() {
-                        let num = parsed_elem.unwrap();
-                        if num != last_elem {
-                            neighbors.push(num);
-                            last_elem = num;
-                        }
-                    };
-                }
-            }
-            
-            if neighbors.len() > 1 {
-                total += neighbors.iter().product::<u32>();
-            }
-        }
-    }
+    schematic
+        .symbols
+        .iter()
+        .map(|&(p, _)| schematic.numbers_around(p).iter().sum::<u32>())
+        .sum()
+}
 
-    total
+fn task_2(input: &str) -> u32 {
+    let schematic = parse_schematic(input);
+
+    schematic
+        .symbols
+        .iter()
+        .filter(|&&(_, c)| c == '*')
+        .map(|&(p, _)| schematic.numbers_around(p))
+        .filter(|numbers| numbers.len() > 1)
+        .map(|numbers| numbers.iter().product::<u32>())
+        .sum()
 }
 
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    if std::env::args().any(|arg| arg == "--stats") {
+        shared::stats::grids(input).print();
+        return;
+    }
 
     println!("task 1: {}", task_1(input));
     println!("task 2: {}", task_2(input));
@@ -103,37 +96,10 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    #[test]
-    fn task_1_works() {
-        let input = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-
-        let result = super::task_1(input);
-        assert_eq!(result, 4361);
-    }
+    use super::*;
 
-    #[test]
-    fn task_2_works() {
-        let input = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-
-        let result = super::task_2(input);
-        assert_eq!(result, 467835);
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
     }
-}
\ No newline at end of file
+}
@@ -2,14 +2,13 @@ use nom::{
     branch::permutation,
     bytes::complete::tag,
     character::complete::{alpha1, digit1, space1},
-    combinator::map_res,
-    combinator::opt,
-    multi::separated_list1,
     sequence::{preceded, separated_pair},
     IResult,
 };
+use serde::{Deserialize, Serialize};
+use shared::{explain::Explained, input::normalize, parse::numbers};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct Card {
     instances: u32,
     winning_numbers: Vec<u32>,
@@ -42,17 +41,6 @@ impl Card {
     }
 }
 
-fn to_u32(input: &str) -> IResult<&str, u32> {
-    map_res(preceded(opt(space1), digit1), str::parse)(input)
-}
-
-// 41 48 83 86 17 -> Vec<u32>
-fn numbers(input: &str) -> IResult<&str, Vec<u32>> {
-    let (input, numbers) = separated_list1(tag(" "), to_u32)(input)?;
-
-    Ok((input, numbers))
-}
-
 // Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53 -> Card
 fn parse_card(input: &str) -> IResult<&str, Card> {
     // let (input, id) = preceded(permutation((tag("Card"), space1)), to_u32)(input)?;
@@ -71,46 +59,98 @@ fn parse_card(input: &str) -> IResult<&str, Card> {
     ))
 }
 
-fn task_1(input: &str) -> u32 {
+fn parse_cards(input: &str) -> Vec<Card> {
     input
         .lines()
         .map(|l| parse_card(l).expect("line should be a valid card").1)
-        .fold(0, |mut acc, i| {
-            acc += i.get_value();
+        .collect()
+}
+
+fn solve_1(input: &str, mut steps: Option<&mut Vec<String>>) -> u32 {
+    parse_cards(input)
+        .into_iter()
+        .enumerate()
+        .fold(0, |mut acc, (i, card)| {
+            let value = card.get_value();
+            if let Some(steps) = &mut steps {
+                steps.push(format!(
+                    "Card {}: {} matches -> {value} points",
+                    i + 1,
+                    card.get_matches().len()
+                ));
+            }
+            acc += value;
             acc
         })
 }
 
-fn task_2(input: &str) -> u32 {
-    let cards: Vec<_> = input
-        .lines()
-        .map(|l| parse_card(l).expect("line should be a valid card").1)
-        .collect();
+fn explain_1(input: &str) -> Explained<u32> {
+    Explained::record(|steps| solve_1(input, Some(steps)))
+}
+
+fn task_1(input: &str) -> u32 {
+    solve_1(input, None)
+}
+
+fn solve_2(input: &str, mut steps: Option<&mut Vec<String>>) -> u32 {
+    let cards = parse_cards(input);
 
     let mut cards_new = cards.clone();
 
     for (i, card) in cards.iter().enumerate() {
         let matches = card.get_matches();
+        let card_instances = cards_new.get(i).expect("should be valid index").instances;
 
         for j in 1..=matches.len() {
-            let card_instances = cards_new.get(i).expect("should be valid index").instances;
             cards_new
                 .get_mut(i + j)
                 .expect("should be valid index")
                 .instances += card_instances
         }
+
+        if let Some(steps) = &mut steps {
+            steps.push(format!(
+                "Card {}: {} matches, {card_instances} instances",
+                i + 1,
+                matches.len()
+            ));
+        }
     }
 
-    cards_new
-        .iter()
-        .fold(0, |mut acc, i| {
-            acc += i.instances;
-            acc
-        })
+    cards_new.iter().fold(0, |mut acc, i| {
+        acc += i.instances;
+        acc
+    })
+}
+
+fn explain_2(input: &str) -> Explained<u32> {
+    Explained::record(|steps| solve_2(input, Some(steps)))
+}
+
+fn task_2(input: &str) -> u32 {
+    solve_2(input, None)
 }
 
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    if std::env::args().any(|arg| arg == "--dump") {
+        let cards = parse_cards(input);
+        println!("{}", serde_json::to_string_pretty(&cards).unwrap());
+        return;
+    }
+
+    if std::env::args().any(|arg| arg == "--explain") {
+        explain_1(input).print("task 1");
+        explain_2(input).print("task 2");
+        return;
+    }
 
     println!("task 1: {}", task_1(input));
     println!("task 2: {}", task_2(input));
@@ -118,31 +158,39 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use crate::{task_2, task_1};
+    use super::*;
+
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
+    }
 
     #[test]
-    fn task_1_works() {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+    fn dump_round_trips() {
+        let cards = parse_cards(include_str!("../examples/example.txt"));
 
-        let result = task_1(input);
-        assert_eq!(result, 13);
+        let json = serde_json::to_string(&cards).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<Card>>(&json).unwrap(), cards);
     }
 
     #[test]
-    fn task_2_works() {
+    fn explain_works() {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
 Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-
-        let result = task_2(input);
-        assert_eq!(result, 30);
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36";
+
+        assert_eq!(explain_1(input).steps[0], "Card 1: 4 matches -> 8 points");
+        assert_eq!(
+            explain_2(input).steps,
+            vec![
+                "Card 1: 4 matches, 1 instances",
+                "Card 2: 2 matches, 2 instances",
+                "Card 3: 2 matches, 4 instances",
+                "Card 4: 1 matches, 8 instances",
+                "Card 5: 0 matches, 14 instances",
+            ]
+        );
     }
 }
@@ -1,16 +1,23 @@
-use std::collections::BTreeSet;
-
+use itertools::Itertools;
 use nom::{
     branch::permutation,
     bytes::complete::tag,
-    character::complete::{alpha1, space1, u64},
+    character::complete::{alpha1, space1},
     combinator::opt,
     multi::separated_list1,
     sequence::preceded,
     IResult,
 };
+use serde::{Deserialize, Serialize};
+use shared::{
+    assumptions::Assumption,
+    input::normalize,
+    parse::{blocks, numbers},
+    ranges::RangeSet,
+    stats::Stats,
+};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 struct Conversion {
     src: u64,
     dest: u64,
@@ -27,15 +34,9 @@ impl Conversion {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 struct AlmanacMap(Vec<Conversion>);
 
-#[derive(Debug, PartialEq)]
-struct ValueRange {
-    start: u64,
-    length: u64,
-}
-
 impl AlmanacMap {
     fn convert(&self, source: u64) -> u64 {
         match self
@@ -49,43 +50,16 @@ impl AlmanacMap {
         }
     }
 
-    fn convert_range(&self, range: ValueRange) -> Vec<ValueRange> {
-        let mut slices = BTreeSet::new();
-        let range_end = range.start + range.length;
-
-        for entry in &self.0 {
-            let source_end = entry.src + entry.offset;
-
-            if range_end < entry.src || range.start > source_end {
-                continue;
-            }
-
-            if entry.src > range.start {
-                slices.insert(entry.src);
-            }
-
-            if source_end < range_end {
-                slices.insert(source_end);
-            }
-        }
-        slices.insert(range_end);
-
-        let mut output = Vec::new();
-        let mut current = range.start;
-
-        for position in slices {
-            output.push(ValueRange {
-                start: self.convert(current),
-                length: position - current,
-            });
-            current = position;
-        }
-
-        output
+    fn convert_ranges(&self, values: &RangeSet<u64>) -> RangeSet<u64> {
+        values.map_piecewise(
+            self.0
+                .iter()
+                .map(|entry| (entry.src..entry.src + entry.offset, entry.dest)),
+        )
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 struct Almanac {
     seeds: Vec<u64>,
     maps: Vec<AlmanacMap>,
@@ -96,22 +70,22 @@ impl Almanac {
         self.maps.iter().fold(seed, |value, map| map.convert(value))
     }
 
-    fn seed_ranges(&self) -> impl Iterator<Item = ValueRange> + '_ {
-        (0..self.seeds.len()).step_by(2).map(|i| ValueRange {
-            start: self.seeds[i],
-            length: self.seeds[i + 1],
-        })
+    fn seed_ranges(&self) -> RangeSet<u64> {
+        (0..self.seeds.len())
+            .step_by(2)
+            .map(|i| self.seeds[i]..self.seeds[i] + self.seeds[i + 1])
+            .collect()
     }
 }
 
 fn parse_seeds(input: &str) -> IResult<&str, Vec<u64>> {
-    let (input, nums) = preceded(tag("seeds: "), separated_list1(tag(" "), u64))(input)?;
+    let (input, nums) = preceded(tag("seeds:"), numbers)(input)?;
     Ok((input, nums))
 }
 
 // 50 98 2 -> Conversion
 fn parse_conversion(input: &str) -> IResult<&str, Conversion> {
-    let (input, nums) = separated_list1(tag(" "), u64)(input)?;
+    let (input, nums) = numbers(input)?;
 
     Ok((
         input,
@@ -141,7 +115,7 @@ fn parse_map(input: &str) -> IResult<&str, AlmanacMap> {
 
 fn parse_almanac(input: &str) -> IResult<&str, Almanac> {
     let (input, seeds) = parse_seeds(input).unwrap();
-    let (input, maps) = separated_list1(tag("\n\n"), parse_map)(input)?;
+    let (input, maps) = blocks(parse_map)(input)?;
 
     Ok((input, Almanac { seeds, maps }))
 }
@@ -160,22 +134,85 @@ fn task_1(input: &str) -> u64 {
 fn task_2(input: &str) -> u64 {
     let almanac = parse_almanac(input).unwrap().1;
 
-    let mut current: Vec<ValueRange> = almanac.seed_ranges().collect();
-    let mut future = Vec::new();
+    almanac
+        .maps
+        .iter()
+        .fold(almanac.seed_ranges(), |values, map| {
+            map.convert_ranges(&values)
+        })
+        .min()
+        .unwrap()
+}
 
-    for map in almanac.maps {
-        for range in current {
-            future.extend(map.convert_range(range));
+fn check(input: &str) -> Vec<Assumption> {
+    let almanac = parse_almanac(input).unwrap().1;
+
+    let odd_seeds = if almanac.seeds.len().is_multiple_of(2) {
+        vec![]
+    } else {
+        vec![format!("{} seed numbers", almanac.seeds.len())]
+    };
+
+    let mut overlaps = vec![];
+    for (i, map) in almanac.maps.iter().enumerate() {
+        let conversions = map.0.iter().sorted_by_key(|c| c.src).collect_vec();
+        for (a, b) in conversions.iter().tuple_windows() {
+            if a.src + a.offset > b.src {
+                overlaps.push(format!(
+                    "map {}: {}..{} overlaps {}..{}",
+                    i + 1,
+                    a.src,
+                    a.src + a.offset,
+                    b.src,
+                    b.src + b.offset
+                ));
+            }
         }
-        current = future;
-        future = Vec::new();
     }
 
-    current.iter().map(|range| range.start).min().unwrap()
+    vec![
+        Assumption::new("seeds come in (start, length) pairs", odd_seeds),
+        Assumption::new("source ranges within a map don't overlap", overlaps),
+    ]
+}
+
+fn stats(input: &str) -> Stats {
+    let almanac = parse_almanac(input).unwrap().1;
+    let conversions: Vec<usize> = almanac.maps.iter().map(|map| map.0.len()).collect();
+
+    let mut stats = Stats::new();
+    stats
+        .add("seeds", almanac.seeds.len())
+        .add("maps", almanac.maps.len())
+        .add("conversions", conversions.iter().sum::<usize>())
+        .add("conversions per map", shared::stats::span(&conversions));
+    stats
 }
 
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    if std::env::args().any(|arg| arg == "--stats") {
+        stats(input).print();
+        return;
+    }
+
+    if std::env::args().any(|arg| arg == "--dump") {
+        let almanac = parse_almanac(input).unwrap().1;
+        println!("{}", serde_json::to_string_pretty(&almanac).unwrap());
+        return;
+    }
+
+    if std::env::args().any(|arg| arg == "--check") {
+        let ok = shared::assumptions::report(&check(input));
+        std::process::exit(if ok { 0 } else { 1 });
+    }
 
     println!("task 1: {}", task_1(input));
     println!("task 2: {}", task_2(input));
@@ -183,86 +220,39 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use crate::*;
+    use super::*;
+
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
+    }
 
     #[test]
-    // #[ignore]
-    fn task_1_works() {
-        let input = "seeds: 79 14 55 13
+    fn stats_works() {
+        let stats = stats(include_str!("../examples/example.txt"));
 
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-
-        let result = task_1(input);
-        assert_eq!(result, 35);
+        let values: Vec<&str> = stats.0.iter().map(|(_, value)| value.as_str()).collect();
+        assert_eq!(values, vec!["4", "7", "18", "2..=4"]);
     }
 
     #[test]
-    fn task_2_works() {
-        let input = "seeds: 79 14 55 13
+    fn dump_round_trips() {
+        let (_, almanac) = parse_almanac(include_str!("../examples/example.txt")).unwrap();
+
+        let json = serde_json::to_string(&almanac).unwrap();
+        assert_eq!(serde_json::from_str::<Almanac>(&json).unwrap(), almanac);
+    }
+
+    #[test]
+    fn check_works() {
+        let input = "seeds: 79 14 55
 
 seed-to-soil map:
 50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-
-        let result = task_2(input);
-        assert_eq!(result, 46);
+52 50 49";
+
+        let result = check(input);
+        assert!(!result[0].holds());
+        assert_eq!(result[1].violations, vec!["map 1: 50..99 overlaps 98..100"]);
     }
 }
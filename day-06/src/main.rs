@@ -1,11 +1,16 @@
 use nom::{
     branch::permutation,
     bytes::complete::tag,
-    character::complete::{alpha1, digit1, multispace0, space1, u64},
+    character::complete::{alpha1, digit1, multispace0, space1},
     multi::separated_list1,
     sequence::preceded,
     IResult,
 };
+use shared::{
+    input::normalize,
+    parse::numbers,
+    variants::{self, Variant},
+};
 
 struct Race {
     time: u64,
@@ -16,16 +21,51 @@ impl Race {
     fn can_be_won(&self, button_time: u64) -> bool {
         (self.time - button_time) * button_time > self.record
     }
+
+    fn count_wins(&self) -> u64 {
+        (1..self.time).fold(
+            0,
+            |acc, time| {
+                if self.can_be_won(time) {
+                    acc + 1
+                } else {
+                    acc
+                }
+            },
+        )
+    }
+
+    // the winning button times lie strictly between the roots of
+    // b * (time - b) = record, symmetric around time / 2
+    fn count_wins_closed_form(&self) -> u64 {
+        let (time, record) = (self.time as f64, self.record as f64);
+        let discriminant = time * time - 4.0 * record;
+        if discriminant < 0.0 {
+            return 0;
+        }
+
+        // the float root can be off by one either way for large inputs
+        let mut first = ((time - discriminant.sqrt()) / 2.0).max(0.0) as u64;
+        while first <= self.time / 2 && !self.can_be_won(first) {
+            first += 1;
+        }
+        while first > 0 && self.can_be_won(first - 1) {
+            first -= 1;
+        }
+
+        if first > self.time / 2 {
+            0
+        } else {
+            self.time - 2 * first + 1
+        }
+    }
 }
 
 fn parse_races(input: &str) -> IResult<&str, Vec<Race>> {
-    let (input, times) = preceded(
-        permutation((alpha1, tag(":"), space1)),
-        separated_list1(space1, u64),
-    )(input)?;
+    let (input, times) = preceded(permutation((alpha1, tag(":"), space1)), numbers)(input)?;
     let (input, records) = preceded(
         permutation((multispace0, alpha1, tag(":"), space1)),
-        separated_list1(space1, u64),
+        numbers,
     )(input)?;
 
     let races = times
@@ -46,21 +86,13 @@ fn parse_races(input: &str) -> IResult<&str, Vec<Race>> {
 fn task_1(input: &str) -> u64 {
     let races = parse_races(input).unwrap().1;
 
-    races
-        .iter()
-        .map(|race| {
-            (1..race.time).fold(
-                0,
-                |acc, time| {
-                    if race.can_be_won(time) {
-                        acc + 1
-                    } else {
-                        acc
-                    }
-                },
-            )
-        })
-        .product()
+    races.iter().map(Race::count_wins).product()
+}
+
+fn task_1_closed_form(input: &str) -> u64 {
+    let races = parse_races(input).unwrap().1;
+
+    races.iter().map(Race::count_wins_closed_form).product()
 }
 
 fn parse_single_race(input: &str) -> IResult<&str, Race> {
@@ -90,44 +122,61 @@ fn parse_single_race(input: &str) -> IResult<&str, Race> {
 fn task_2(input: &str) -> u64 {
     let race = parse_single_race(input).unwrap().1;
 
-    (1..race.time).fold(
-        0,
-        |acc, time| {
-            if race.can_be_won(time) {
-                acc + 1
-            } else {
-                acc
-            }
-        },
-    )
+    race.count_wins()
+}
+
+fn task_2_closed_form(input: &str) -> u64 {
+    let race = parse_single_race(input).unwrap().1;
+
+    race.count_wins_closed_form()
 }
 
 fn main() {
-    let input = include_str!["./data.txt"];
+    let input = &normalize(include_str!["./data.txt"]);
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    let part_1 = [
+        Variant::new("loop", task_1),
+        Variant::new("closed-form", task_1_closed_form),
+    ];
+    let part_2 = [
+        Variant::new("loop", task_2),
+        Variant::new("closed-form", task_2_closed_form),
+    ];
+
+    if std::env::args().any(|arg| arg == "--verify") {
+        let part_1_ok = variants::verify("task 1", input, &part_1);
+        let part_2_ok = variants::verify("task 2", input, &part_2);
+        std::process::exit(if part_1_ok && part_2_ok { 0 } else { 1 });
+    }
 
-    println!("task 1: {}", task_1(input));
-    println!("task 2: {}", task_2(input));
+    variants::run("task 1", input, &part_1);
+    variants::run("task 2", input, &part_2);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn task_1_works() {
-        let input = "Time:      7  15   30
-Distance:  9  40  200";
-
-        let result = task_1(input);
-        assert_eq![result, 288];
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
+        part_1 as part_1_closed_form => task_1_closed_form,
+        part_2 as part_2_closed_form => task_2_closed_form,
     }
 
     #[test]
-    fn task_2_works() {
-        let input = "Time:      7  15   30
-Distance:  9  40  200";
-
-        let result = task_2(input);
-        assert_eq![result, 71503];
+    fn closed_form_works() {
+        // no wins, a single win at time / 2, and every button time winning
+        for (time, record, wins) in [(4, 4, 0), (4, 3, 1), (5, 0, 4), (7, 9, 4)] {
+            let race = Race { time, record };
+            assert_eq!(race.count_wins(), wins);
+            assert_eq!(race.count_wins_closed_form(), wins);
+        }
     }
 }
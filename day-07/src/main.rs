@@ -1,8 +1,22 @@
+use shared::input::normalize;
+
 mod part1;
 mod part2;
 
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| part1::task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| part2::task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    if std::env::args().any(|arg| arg == "--dump") {
+        let hands = serde_json::json!({ "part1": part1::dump(input), "part2": part2::dump(input) });
+        println!("{hands:#}");
+        return;
+    }
 
     println!("task 1: {}", part1::task_1(input));
     println!("task 2: {}", part2::task_2(input));
@@ -8,8 +8,9 @@ use nom::{
     sequence::separated_pair,
     IResult,
 };
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 enum HandType {
     HighCard,
     OnePair,
@@ -20,7 +21,7 @@ enum HandType {
     FiveOfAKind,
 }
 
-#[derive(Debug, Eq, PartialEq, Ord)]
+#[derive(Debug, Eq, PartialEq, Ord, Serialize, Deserialize)]
 struct Hand {
     cards: Vec<u32>,
     t: HandType,
@@ -108,6 +109,10 @@ fn parse_hands(input: &str) -> IResult<&str, Vec<Hand>> {
     Ok((input, hands))
 }
 
+pub fn dump(input: &str) -> serde_json::Value {
+    serde_json::to_value(parse_hands(input).unwrap().1).unwrap()
+}
+
 pub fn task_1(input: &str) -> u32 {
     let mut hands = parse_hands(input).unwrap().1;
     hands.sort();
@@ -117,15 +122,17 @@ pub fn task_1(input: &str) -> u32 {
 
 #[cfg(test)]
 mod test {
+    use super::*;
+
+    shared::example_tests! {
+        part_1 => task_1,
+    }
+
     #[test]
-    fn task_1_works() {
-        let input = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-
-        let result = super::task_1(input);
-        assert_eq!(result, 6440);
+    fn dump_round_trips() {
+        let (_, hands) = parse_hands(include_str!("../examples/example.txt")).unwrap();
+
+        let json = serde_json::to_string(&hands).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<Hand>>(&json).unwrap(), hands);
     }
-}
\ No newline at end of file
+}
@@ -2,27 +2,34 @@ use std::{vec, collections::HashMap};
 
 use itertools::Itertools;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{alphanumeric1, anychar, multispace1},
+    character::complete::{anychar, multispace1},
     combinator::map_res,
     multi::{many1, separated_list1},
-    sequence::{delimited, preceded, separated_pair},
+    sequence::preceded,
     IResult,
 };
+use serde::{Deserialize, Serialize};
+use shared::{assumptions::Assumption, input::normalize, parse::record, stats::Stats};
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 enum Direction {
     L,
     R,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct Node {
     value: String,
     l: String,
     r: String,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Network {
+    directions: Vec<Direction>,
+    nodes: Vec<Node>,
+}
+
 fn parse_direction(input: &str) -> IResult<&str, Direction> {
     map_res(anychar, |c| match c {
         'R' => Ok(Direction::R),
@@ -38,22 +45,14 @@ fn parse_directions(input: &str) -> IResult<&str, Vec<Direction>> {
 }
 
 fn parse_node(input: &str) -> IResult<&str, Node> {
-    let (input, node) = separated_pair(
-        alphanumeric1,
-        tag(" = "),
-        delimited(
-            tag("("),
-            separated_pair(alphanumeric1, tag(", "), alphanumeric1),
-            tag(")"),
-        ),
-    )(input)?;
+    let (input, (value, (l, r))) = record(input)?;
 
     Ok((
         input,
         Node {
-            value: node.0.to_owned(),
-            l: node.1 .0.to_owned(),
-            r: node.1 .1.to_owned(),
+            value: value.to_owned(),
+            l: l.to_owned(),
+            r: r.to_owned(),
         },
     ))
 }
@@ -64,6 +63,13 @@ fn parse_tree(input: &str) -> IResult<&str, Vec<Node>> {
     Ok((input, nodes))
 }
 
+fn parse_network(input: &str) -> IResult<&str, Network> {
+    let (input, directions) = parse_directions(input)?;
+    let (input, nodes) = parse_tree(input)?;
+
+    Ok((input, Network { directions, nodes }))
+}
+
 fn task_1(input: &str) -> u32 {
     let (input, directions) = parse_directions(input).unwrap();
     let tree = parse_tree(input).unwrap().1;
@@ -158,8 +164,134 @@ fn task_2(input: &str) -> u64 {
     lcm(zs) as u64
 }
 
+// walks from `node` until the next Z node, giving up once every (node, direction) state was seen
+fn next_z<'a>(
+    nodes: &HashMap<&str, &'a Node>,
+    directions: &[Direction],
+    node: &'a Node,
+    start: usize,
+) -> Option<(usize, &'a Node)> {
+    let mut curr_node = node;
+    for i in start..start + nodes.len() * directions.len() {
+        curr_node = match directions[i % directions.len()] {
+            Direction::R => nodes[curr_node.r.as_str()],
+            Direction::L => nodes[curr_node.l.as_str()],
+        };
+        if curr_node.value.ends_with('Z') {
+            return Some((i + 1, curr_node));
+        }
+    }
+    None
+}
+
+// walks on from `z`, reached after `first` steps, until it is back at `z` at the same point in the
+// directions, and gives every step count on the way with the node it ends on
+fn cycle<'a>(
+    nodes: &HashMap<&str, &'a Node>,
+    directions: &[Direction],
+    z: &'a Node,
+    first: usize,
+) -> Option<Vec<(usize, &'a Node)>> {
+    let mut curr_node = z;
+    let mut steps = vec![];
+    for i in first..first + nodes.len() * directions.len() {
+        curr_node = match directions[i % directions.len()] {
+            Direction::R => nodes[curr_node.r.as_str()],
+            Direction::L => nodes[curr_node.l.as_str()],
+        };
+        steps.push((i + 1, curr_node));
+        if curr_node.value == z.value && (i + 1) % directions.len() == first % directions.len() {
+            return Some(steps);
+        }
+    }
+    None
+}
+
+fn check(input: &str) -> Vec<Assumption> {
+    let (input, directions) = parse_directions(input).unwrap();
+    let tree = parse_tree(input).unwrap().1;
+    let nodes: HashMap<&str, &Node> = tree.iter().map(|n| (n.value.as_str(), n)).collect();
+
+    let mut periods = vec![];
+    for start in tree.iter().filter(|n| n.value.ends_with('A')) {
+        let Some((first, z)) = next_z(&nodes, &directions, start, 0) else {
+            periods.push(format!("{} never reaches a Z node", start.value));
+            continue;
+        };
+        // the walk repeats from here on, so one cycle shows every later arrival
+        let Some(cycle) = cycle(&nodes, &directions, z, first) else {
+            periods.push(format!(
+                "{} reaches {} after {first} steps but never again at the same point in the directions",
+                start.value, z.value
+            ));
+            continue;
+        };
+
+        let wrong = cycle
+            .iter()
+            .find(|(steps, node)| node.value.ends_with('Z') != (steps % first == 0));
+        match wrong {
+            Some((steps, node)) if node.value.ends_with('Z') => periods.push(format!(
+                "{} reaches {} after {steps} steps, which is not a multiple of {first}",
+                start.value, node.value
+            )),
+            Some((steps, node)) => periods.push(format!(
+                "{} is at {} instead of a Z node after {steps} steps",
+                start.value, node.value
+            )),
+            None => {}
+        }
+    }
+
+    vec![Assumption::new(
+        "every ghost is on a Z node exactly at the multiples of its first arrival",
+        periods,
+    )]
+}
+
+fn stats(input: &str) -> Stats {
+    let network = parse_network(input).unwrap().1;
+    let ending_in = |c: char| {
+        network
+            .nodes
+            .iter()
+            .filter(|n| n.value.ends_with(c))
+            .count()
+    };
+
+    let mut stats = Stats::new();
+    stats
+        .add("directions", network.directions.len())
+        .add("nodes", network.nodes.len())
+        .add("nodes ending in A", ending_in('A'))
+        .add("nodes ending in Z", ending_in('Z'));
+    stats
+}
+
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    if std::env::args().any(|arg| arg == "--stats") {
+        stats(input).print();
+        return;
+    }
+
+    if std::env::args().any(|arg| arg == "--dump") {
+        let network = parse_network(input).unwrap().1;
+        println!("{}", serde_json::to_string_pretty(&network).unwrap());
+        return;
+    }
+
+    if std::env::args().any(|arg| arg == "--check") {
+        let ok = shared::assumptions::report(&check(input));
+        std::process::exit(if ok { 0 } else { 1 });
+    }
 
     println!("task 1: {}", task_1(input));
     println!("task 2: {}", task_2(input));
@@ -169,49 +301,75 @@ fn main() {
 mod test {
     use super::*;
 
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
+    }
+
     #[test]
-    fn task_1_works() {
-        {
-            let input = "RL
-    
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-
-            let result = task_1(input);
-            assert_eq!(result, 2);
-        }
+    fn stats_works() {
+        let stats = stats(include_str!("../examples/example-3.txt"));
 
-        {
-            let input = "LLR
-    
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
+        let values: Vec<&str> = stats.0.iter().map(|(_, value)| value.as_str()).collect();
+        assert_eq!(values, vec!["2", "8", "2", "2"]);
+    }
 
-            let result = task_1(input);
-            assert_eq!(result, 6);
-        }
+    #[test]
+    fn dump_round_trips() {
+        let (_, network) = parse_network(include_str!("../examples/example-2.txt")).unwrap();
+
+        let json = serde_json::to_string(&network).unwrap();
+        assert_eq!(serde_json::from_str::<Network>(&json).unwrap(), network);
     }
 
     #[test]
-    fn task_2_works() {
-        let input = "LR
+    fn check_works() {
+        let input = "L
 
-11A = (11B, XXX)
-11B = (XXX, 11Z)
+11A = (11Z, XXX)
 11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
+11B = (22Z, XXX)
+22Z = (11Z, XXX)
+XXX = (XXX, XXX)";
+
+        let result = check(input);
+        assert_eq!(
+            result[0].violations,
+            vec!["11A is at 11B instead of a Z node after 2 steps"]
+        );
+
+        let input = "L
+
+11A = (11B, XXX)
+11B = (11Z, XXX)
+11Z = (22Z, XXX)
+22Z = (11C, XXX)
+11C = (11Z, XXX)
+XXX = (XXX, XXX)";
+
+        let result = check(input);
+        assert_eq!(
+            result[0].violations,
+            vec!["11A reaches 22Z after 3 steps, which is not a multiple of 2"]
+        );
+
+        // reaches 11Z at steps 1 and 2, but never again
+        let input = "LR
+
+11A = (11Z, XXX)
+11Z = (XXX, 11Z)
 XXX = (XXX, XXX)";
 
-        let result = task_2(input);
-        assert_eq!(result, 6);
+        let result = check(input);
+        assert_eq!(
+            result[0].violations,
+            vec![
+                "11A reaches 11Z after 1 steps but never again at the same point in the directions"
+            ]
+        );
+
+        // 22A reaches 22Z after 3 steps with 2 directions, but every third step after that
+        let result = check(include_str!("../examples/example-3.txt"));
+        assert!(result[0].holds());
     }
 }
@@ -1,12 +1,9 @@
 use itertools::Itertools;
-use nom::{
-    character::complete::{i32, multispace1, space1},
-    multi::separated_list1,
-    IResult,
-};
+use nom::{character::complete::multispace1, multi::separated_list1, IResult};
+use shared::{input::normalize, parse::numbers};
 
 fn parse_sequences(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
-    let (input, sequences) = separated_list1(multispace1, separated_list1(space1, i32))(input)?;
+    let (input, sequences) = separated_list1(multispace1, numbers)(input)?;
 
     Ok((input, sequences))
 }
@@ -84,7 +81,13 @@ fn task_2(input: &str) -> i32 {
 }
 
 fn main() {
-    let input = include_str!["./data.txt"];
+    let input = &normalize(include_str!["./data.txt"]);
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
 
     println!("task 1: {}", task_1(input));
     println!("task 2: {}", task_2(input));
@@ -94,23 +97,8 @@ fn main() {
 mod test {
     use super::*;
 
-    #[test]
-    fn task_1_works() {
-        let input = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
-
-        let result = task_1(input);
-        assert_eq![result, 114];
-    }
-
-    #[test]
-    fn task_2_works() {
-        let input = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
-
-        let result = task_2(input);
-        assert_eq![result, 2];
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
     }
 }
@@ -1,17 +1,20 @@
-use std::vec;
+use std::{
+    collections::{HashMap, HashSet},
+    vec,
+};
 
 use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{anychar, one_of},
-    combinator::map_res,
-    multi::{many1, separated_list1},
-    IResult,
+use nom::IResult;
+use serde::{Deserialize, Serialize};
+use shared::{
+    assumptions::Assumption,
+    geometry::{Dir, Pos},
+    input::normalize,
+    parse::grid,
+    variants::{self, Variant},
 };
 
-type Vec2 = (i32, i32);
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 enum TileType {
     NorthSouth,
     EastWest,
@@ -23,15 +26,15 @@ enum TileType {
     Ground,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 struct Tile {
     t: TileType,
-    position: Vec2,
+    position: Pos,
 }
 
 impl Tile {
     fn is_above(&self, other: Tile) -> bool {
-        self.position.1 > other.position.1 && self.position.0 == other.position.0
+        self.position.y > other.position.y && self.position.x == other.position.x
     }
 
     fn process_north(&self, other: Tile) -> bool {
@@ -49,7 +52,7 @@ impl Tile {
     }
 
     fn is_below(&self, other: Tile) -> bool {
-        self.position.1 < other.position.1 && self.position.0 == other.position.0
+        self.position.y < other.position.y && self.position.x == other.position.x
     }
 
     fn process_south(&self, other: Tile) -> bool {
@@ -67,7 +70,7 @@ impl Tile {
     }
 
     fn is_right(&self, other: Tile) -> bool {
-        self.position.0 > other.position.0 && self.position.1 == other.position.1
+        self.position.x > other.position.x && self.position.y == other.position.y
     }
 
     fn process_west(&self, other: Tile) -> bool {
@@ -85,7 +88,7 @@ impl Tile {
     }
 
     fn is_left(&self, other: Tile) -> bool {
-        self.position.0 < other.position.0 && self.position.1 == other.position.1
+        self.position.x < other.position.x && self.position.y == other.position.y
     }
 
     fn process_east(&self, other: Tile) -> bool {
@@ -129,7 +132,7 @@ impl Tile {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct Maze(Vec<Tile>);
 
 impl Maze {
@@ -140,18 +143,18 @@ impl Maze {
             .copied()
     }
 
-    fn get_by_position(&self, position: Vec2) -> Option<Tile> {
+    fn get_by_position(&self, position: Pos) -> Option<Tile> {
         self.0
             .iter()
-            .find(|tile| tile.position == (position.0, position.1))
+            .find(|tile| tile.position == position)
             .copied()
     }
 
-    fn get_row(&self, position: Vec2) -> Option<Vec<Tile>> {
+    fn get_row(&self, position: Pos) -> Option<Vec<Tile>> {
         let row = self
             .0
             .iter()
-            .filter(|tile| tile.position.1 == position.1)
+            .filter(|tile| tile.position.y == position.y)
             .copied()
             .collect_vec();
         if !row.is_empty() {
@@ -162,41 +165,12 @@ impl Maze {
     }
 
     fn get_neighborhood(&self, point: Tile) -> Vec<Tile> {
-        let mut neighborhood: Vec<Tile> = vec![];
-        if let Some(n) = self.get_by_position((point.position.0 - 1, point.position.1 - 1)) {
-            // Top left
-            neighborhood.push(n);
-        }
-        if let Some(n) = self.get_by_position((point.position.0, point.position.1 - 1)) {
-            // Top center
-            neighborhood.push(n);
-        }
-        if let Some(n) = self.get_by_position((point.position.0 + 1, point.position.1 - 1)) {
-            // Top right
-            neighborhood.push(n);
-        }
-        if let Some(n) = self.get_by_position((point.position.0 - 1, point.position.1)) {
-            // Middle left
-            neighborhood.push(n);
-        }
-        if let Some(n) = self.get_by_position((point.position.0 + 1, point.position.1)) {
-            // Middle right
-            neighborhood.push(n);
-        }
-        if let Some(n) = self.get_by_position((point.position.0 - 1, point.position.1 + 1)) {
-            // Bottom left
-            neighborhood.push(n);
-        }
-        if let Some(n) = self.get_by_position((point.position.0, point.position.1 + 1)) {
-            // Bottom center
-            neighborhood.push(n);
-        }
-        if let Some(n) = self.get_by_position((point.position.0 + 1, point.position.1 + 1)) {
-            // Bottom right
-            neighborhood.push(n);
-        }
-
-        neighborhood
+        point
+            .position
+            .neighbors8()
+            .into_iter()
+            .filter_map(|position| self.get_by_position(position))
+            .collect()
     }
 
     fn bfs(&self, start: Tile) -> Vec<Tile> {
@@ -270,56 +244,30 @@ impl Maze {
     }
 }
 
-fn parse_tile(input: &str, position: Vec2) -> IResult<&str, Tile> {
-    map_res(anychar, |char| match char {
-        '|' => Ok(Tile {
-            t: TileType::NorthSouth,
-            position,
-        }),
-        '-' => Ok(Tile {
-            t: TileType::EastWest,
-            position,
-        }),
-        'L' => Ok(Tile {
-            t: TileType::NorthEast,
-            position,
-        }),
-        'J' => Ok(Tile {
-            t: TileType::NorthWest,
-            position,
-        }),
-        '7' => Ok(Tile {
-            t: TileType::SouthWest,
-            position,
-        }),
-        'F' => Ok(Tile {
-            t: TileType::SouthEast,
-            position,
-        }),
-        '.' => Ok(Tile {
-            t: TileType::Ground,
-            position,
-        }),
-        'S' => Ok(Tile {
-            t: TileType::Start,
-            position,
-        }),
-        _ => Err("not a valid char"),
-    })(input)
+fn parse_tile_type(char: char) -> Option<TileType> {
+    match char {
+        '|' => Some(TileType::NorthSouth),
+        '-' => Some(TileType::EastWest),
+        'L' => Some(TileType::NorthEast),
+        'J' => Some(TileType::NorthWest),
+        '7' => Some(TileType::SouthWest),
+        'F' => Some(TileType::SouthEast),
+        '.' => Some(TileType::Ground),
+        'S' => Some(TileType::Start),
+        _ => None,
+    }
 }
 
 fn parse_maze(input: &str) -> IResult<&str, Maze> {
-    let c = ['|', '-', 'L', 'J', '7', 'F', '.', 'S'];
-    let (input, chars) = separated_list1(tag("\n"), many1(one_of(&c[..])))(input)?;
+    let (input, rows) = grid(parse_tile_type)(input)?;
     let mut tiles: Vec<Tile> = vec![];
 
-    for (x, col) in chars.iter().enumerate() {
-        for (y, char) in col.iter().filter(|c| **c != '\n').enumerate() {
-            tiles.push(
-                parse_tile(&char.to_string(), (y as i32, x as i32))
-                    .unwrap()
-                    .1,
-            );
+    for (y, row) in rows.iter().enumerate() {
+        for (x, t) in row.iter().enumerate() {
+            tiles.push(Tile {
+                t: *t,
+                position: Pos::new(x as i32, y as i32),
+            });
         }
     }
 
@@ -345,69 +293,224 @@ fn task_2(input: &str) -> u32 {
         .fold(0, |acc, _| acc + 1)
 }
 
+// directions a pipe connects to
+fn openings(t: TileType) -> Vec<Dir> {
+    match t {
+        TileType::NorthSouth => vec![Dir::North, Dir::South],
+        TileType::EastWest => vec![Dir::East, Dir::West],
+        TileType::NorthEast => vec![Dir::North, Dir::East],
+        TileType::NorthWest => vec![Dir::North, Dir::West],
+        TileType::SouthWest => vec![Dir::South, Dir::West],
+        TileType::SouthEast => vec![Dir::South, Dir::East],
+        TileType::Start | TileType::Ground => vec![],
+    }
+}
+
+fn connects_to_start(tiles: &HashMap<Pos, TileType>, start: Pos, dir: Dir) -> bool {
+    tiles
+        .get(&start.step(dir))
+        .is_some_and(|t| openings(*t).contains(&dir.opposite()))
+}
+
+// follows the pipes from S until it is back at S, returning the loop in
+// order or the position of the last pipe before it breaks off
+fn trace_loop(tiles: &HashMap<Pos, TileType>, start: Pos) -> Result<Vec<Pos>, Pos> {
+    let dir = Dir::ALL
+        .into_iter()
+        .find(|d| connects_to_start(tiles, start, *d))
+        .ok_or(start)?;
+    let mut path = vec![start];
+    let mut position = start.step(dir);
+
+    while position != start {
+        let previous = *path.last().unwrap();
+        path.push(position);
+        // only a pipe that connects back can be next, so the walk can't
+        // wander into a loop that S is not on
+        position = openings(tiles[&position])
+            .into_iter()
+            .find(|d| position.step(*d) != previous)
+            .filter(|d| {
+                let next = position.step(*d);
+                next == start
+                    || tiles
+                        .get(&next)
+                        .is_some_and(|t| openings(*t).contains(&d.opposite()))
+            })
+            .map(|d| position.step(d))
+            .ok_or(position)?;
+    }
+
+    Ok(path)
+}
+
+fn loop_through_start(input: &str) -> Vec<Pos> {
+    let maze = parse_maze(input).unwrap().1;
+    let tiles: HashMap<Pos, TileType> = maze.0.iter().map(|tile| (tile.position, tile.t)).collect();
+
+    trace_loop(&tiles, maze.find_start().unwrap().position).unwrap()
+}
+
+fn task_1_shoelace(input: &str) -> u32 {
+    (loop_through_start(input).len() / 2) as u32
+}
+
+fn task_2_shoelace(input: &str) -> u32 {
+    let path = loop_through_start(input);
+
+    // shoelace formula for the area between the centres of the loop's tiles
+    let double_area = path
+        .iter()
+        .circular_tuple_windows()
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<i32>()
+        .abs();
+
+    // Pick's theorem: area = interior + boundary / 2 - 1
+    ((double_area - path.len() as i32) / 2 + 1) as u32
+}
+
+fn check(input: &str) -> Vec<Assumption> {
+    let maze = parse_maze(input).unwrap().1;
+    let tiles: HashMap<Pos, TileType> = maze.0.iter().map(|tile| (tile.position, tile.t)).collect();
+
+    let starts = maze
+        .0
+        .iter()
+        .filter(|tile| tile.t == TileType::Start)
+        .count();
+    let Some(start) = maze.find_start() else {
+        return vec![Assumption::new(
+            "exactly one loop runs through S",
+            vec!["found no start tile".to_string()],
+        )];
+    };
+
+    let connections = Dir::ALL
+        .iter()
+        .filter(|d| connects_to_start(&tiles, start.position, **d))
+        .count();
+
+    let mut single_loop = vec![];
+    if starts != 1 {
+        single_loop.push(format!("found {starts} start tiles"));
+    }
+    if connections != 2 {
+        single_loop.push(format!("{connections} pipes connect to S"));
+    }
+
+    let mut path = HashSet::from([start.position]);
+    if connections > 0 {
+        match trace_loop(&tiles, start.position) {
+            Ok(positions) => path.extend(positions),
+            Err(position) => {
+                single_loop.push(format!("the loop from S breaks off at {position}"));
+            }
+        }
+    }
+
+    // bfs follows every pipe next to S, not just the two that connect to it
+    let stray_pipes = Dir::ALL
+        .iter()
+        .filter(|d| !connects_to_start(&tiles, start.position, **d))
+        .map(|d| start.position.step(*d))
+        .filter(|p| tiles.get(p).is_some_and(|t| *t != TileType::Ground) && !path.contains(p))
+        .map(|p| format!("pipe at {p} touches S but is not on its loop"))
+        .collect_vec();
+
+    vec![
+        Assumption::new("exactly one loop runs through S", single_loop),
+        Assumption::new("every pipe touching S is part of the loop", stray_pipes),
+    ]
+}
+
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    if std::env::args().any(|arg| arg == "--stats") {
+        shared::stats::grids(input).print();
+        return;
+    }
+
+    let part_1 = [
+        Variant::new("bfs", task_1),
+        Variant::new("shoelace", task_1_shoelace),
+    ];
+    let part_2 = [
+        Variant::new("bfs", task_2),
+        Variant::new("shoelace", task_2_shoelace),
+    ];
+
+    if std::env::args().any(|arg| arg == "--dump") {
+        let maze = parse_maze(input).unwrap().1;
+        println!("{}", serde_json::to_string_pretty(&maze).unwrap());
+        return;
+    }
 
-    println!("task 1: {}", task_1(input));
-    println!("task 2: {}", task_2(input));
+    if std::env::args().any(|arg| arg == "--check") {
+        let ok = shared::assumptions::report(&check(input));
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
+    if std::env::args().any(|arg| arg == "--verify") {
+        let part_1_ok = variants::verify("task 1", input, &part_1);
+        let part_2_ok = variants::verify("task 2", input, &part_2);
+        std::process::exit(if part_1_ok && part_2_ok { 0 } else { 1 });
+    }
+
+    variants::run("task 1", input, &part_1);
+    variants::run("task 2", input, &part_2);
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
+        part_1 as part_1_shoelace => task_1_shoelace,
+        part_2 as part_2_shoelace => task_2_shoelace,
+    }
+
     #[test]
-    fn task_1_works() {
-        let input = "7-F7-
-.FJ|7
-SJLL7
-|F--J
-LJ.LJ";
-
-        let result = task_1(input);
-        assert_eq!(result, 8);
+    fn dump_round_trips() {
+        let (_, maze) = parse_maze(include_str!("../examples/example-2.txt")).unwrap();
+
+        let json = serde_json::to_string(&maze).unwrap();
+        assert_eq!(serde_json::from_str::<Maze>(&json).unwrap(), maze);
     }
 
     #[test]
-    fn task_2_works() {
-        let input = "...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-...........";
-
-        let result = task_2(input);
-        assert_eq!(result, 4);
-
-        let input = "..........
-.S------7.
-.|F----7|.
-.||....||.
-.||....||.
-.|L-7F-J|.
-.|..||..|.
-.L--JL--J.
-..........";
-
-        let result = task_2(input);
-        assert_eq!(result, 4);
-
-        let input = "FF7FSF7F7F7F7F7F---7
-L|LJ||||||||||||F--J
-FL-7LJLJ||||||LJL-77
-F--JF--7||LJLJ7F7FJ-
-L---JF-JLJ.||-FJLJJ7
-|F|F-JF---7F7-L7L|7|
-|FFJF7L7F-JF7|JL---7
-7-L-JL7||F7|L7F-7F7|
-L.L7LFJ|||||FJL7||LJ
-L7JLJL-JLJLJL--JLJ.L";
-
-        let result = task_2(input);
-        assert_eq!(result, 10);
+    fn check_works() {
+        let input = "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+        let result = check(input);
+        assert!(result[0].holds());
+        assert_eq!(
+            result[1].violations,
+            vec![
+                "pipe at (1, 0) touches S but is not on its loop",
+                "pipe at (0, 1) touches S but is not on its loop"
+            ]
+        );
+
+        // the 7 at (2, 0) leads to an F that doesn't connect back, and
+        // past it to a loop without S
+        let result = check("S-7.\n|.F7\n|.LJ\nL...");
+        assert_eq!(
+            result[0].violations,
+            vec!["the loop from S breaks off at (2, 0)"]
+        );
     }
 }
@@ -1,23 +1,6 @@
 use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::anychar,
-    combinator::map_res,
-    multi::{many1, separated_list1},
-    IResult,
-};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Pos {
-    x: i32,
-    y: i32,
-}
-
-impl Pos {
-    fn of(x: i32, y: i32) -> Self {
-        Pos { x, y }
-    }
-}
+use nom::IResult;
+use shared::{geometry::Pos, input::normalize, parse::grid};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Field {
@@ -76,7 +59,7 @@ impl Map {
         let mut y = 0;
         let line_len = self.get_row(0).unwrap().len() - 1;
         std::iter::from_fn(move || {
-            let pos = Pos::of(x, y);
+            let pos = Pos::new(x, y);
             if let Some(field) = self.get(pos) {
                 if x == line_len as i32 {
                     x = 0;
@@ -95,7 +78,7 @@ impl Map {
         let mut positions: Vec<Pos> = vec![];
         for (item, Pos { x, y }) in self.iter_pos() {
             if item == Field::Galaxy {
-                positions.push(Pos::of(x as i32, y as i32))
+                positions.push(Pos::new(x as i32, y as i32))
             }
         }
 
@@ -144,16 +127,11 @@ impl Map {
 }
 
 fn parse_input(input: &str) -> IResult<&str, Map> {
-    let (input, galaxies) = separated_list1(
-        tag("\n"),
-        many1(|line| {
-            map_res(anychar, |char| match char {
-                '.' => Ok(Field::Empty),
-                '#' => Ok(Field::Galaxy),
-                _ => Err("not a valid character"),
-            })(line)
-        }),
-    )(input)?;
+    let (input, galaxies) = grid(|char| match char {
+        '.' => Some(Field::Empty),
+        '#' => Some(Field::Galaxy),
+        _ => None,
+    })(input)?;
 
     Ok((input, Map(galaxies)))
 }
@@ -166,7 +144,7 @@ fn task_1(input: &str) -> u32 {
     galaxies
         .iter()
         .tuple_combinations()
-        .map(|(a, b)| a.x.abs_diff(b.x) + a.y.abs_diff(b.y))
+        .map(|(a, b)| a.manhattan(*b))
         .sum()
 }
 
@@ -189,7 +167,7 @@ fn task_2(input: &str, expansion_factor: u32) -> u64 {
                     .iter()
                     .fold(0, |acc, n| if n < &&a.y { acc + 1 } else { acc }),
             );
-            let shifted_a = (
+            let shifted_a = Pos::new(
                 a.x + (expansions_a.0 * (expansion_factor-1)) as i32,
                 a.y + (expansions_a.1 * (expansion_factor-1)) as i32,
             );
@@ -204,18 +182,29 @@ fn task_2(input: &str, expansion_factor: u32) -> u64 {
                     .iter()
                     .fold(0, |acc, n| if n < &&b.y { acc + 1 } else { acc }),
             );
-            let shifted_b = (
+            let shifted_b = Pos::new(
                 b.x + (expansions_b.0 * (expansion_factor-1)) as i32,
                 b.y + (expansions_b.1 * (expansion_factor-1)) as i32,
             );
 
-            (shifted_a.0.abs_diff(shifted_b.0) + shifted_a.1.abs_diff(shifted_b.1)) as u64
+            shifted_a.manhattan(shifted_b) as u64
         })
         .sum()
 }
 
 fn main() {
-    let input = include_str!["./data.txt"];
+    let input = &normalize(include_str!["./data.txt"]);
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input, 1_000_000).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    if std::env::args().any(|arg| arg == "--stats") {
+        shared::stats::grids(input).print();
+        return;
+    }
 
     println!("task 1: {}", task_1(input));
     println!("task 2: {}", task_2(input, 1_000_000));
@@ -225,40 +214,9 @@ fn main() {
 mod test {
     use super::*;
 
-    #[test]
-    fn task_1_works() {
-        let input = "...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....";
-
-        let result = task_1(input);
-        assert_eq!(result, 374);
-    }
-
-    #[test]
-    fn task_2_works() {
-        let input = "...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....";
-
-        let result = task_2(input, 10);
-        assert_eq!(result, 1030);
-
-        let result = task_2(input, 100);
-        assert_eq!(result, 8410);
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2_x10 => |input| task_2(input, 10),
+        part_2_x100 => |input| task_2(input, 100),
     }
 }
@@ -1,64 +1,66 @@
 use std::iter;
-use cached::proc_macro::cached;
 
 use itertools::Itertools;
-
-#[cached]
-fn count(str: String, groups: Vec<u32>) -> u64 {
-    if str == "" {
-        if groups.is_empty() {
-            // no springs left and expecting none
-            return 1;
-        } else {
-            // no springs left but expecting more
-            return 0;
+use shared::{
+    input::normalize,
+    memo::Memo,
+    stats::{self, Stats},
+};
+
+// a suffix of one line's springs and groups is identified by how much of
+// each is left, so the memo is keyed by the two lengths
+fn count(memo: &mut Memo<(usize, usize), u64>, springs: &[u8], groups: &[u32]) -> u64 {
+    memo.get((springs.len(), groups.len()), |memo| {
+        if springs.is_empty() {
+            if groups.is_empty() {
+                // no springs left and expecting none
+                return 1;
+            } else {
+                // no springs left but expecting more
+                return 0;
+            }
         }
-    }
-    if groups.is_empty() {
-        if str.contains('#') {
-            // expecting no more groups but str still contains groups
-            return 0;
-        } else {
-            // expecting no more groups and str doesnt contain any
-            return 1;
+        if groups.is_empty() {
+            if springs.contains(&b'#') {
+                // expecting no more groups but springs still contain groups
+                return 0;
+            } else {
+                // expecting no more groups and springs dont contain any
+                return 1;
+            }
         }
-    }
-    
-    let mut result = 0;
 
-    // case 1: operational spring
-    if ['.', '?'].contains(&str.chars().next().unwrap()) {
-        // skip operational spring and check for the rest of string
-        result += count(str.get(1..).unwrap().to_string(), groups.clone());
-    }
+        let mut result = 0;
 
-    // case 2: broken spring
-    if ['#', '?'].contains(&str.chars().next().unwrap()) {
-        // only valid if:
-        // - there are enough springs left
-        // - the required block size can be achived (no '.' in first n chars of str)
-        // - spring after block must be '.' (either no springs left or next char is not '#')
-        if groups[0] as usize <= str.len()
-            && !str.get(..groups[0] as usize).unwrap().contains('.')
-            && (groups[0] as usize == str.len()
-                || str.chars().collect_vec()[groups[0] as usize] != '#')
-        {
-            // the block is valid -> remove n+1 chars from string (because after group there must be '.') and the first group size from groups
-            if let Some(str) = str.get((groups[0] + 1) as usize..) {
-                result += count(
-                    str.to_string(),
-                    groups[1..].to_vec(),
-                )
-            } else {
-                result += count(
-                    "".to_string(),
-                    groups[1..].to_vec(),
-                )
+        // case 1: operational spring
+        if [b'.', b'?'].contains(&springs[0]) {
+            // skip operational spring and check for the rest of springs
+            result += count(memo, &springs[1..], groups);
+        }
+
+        // case 2: broken spring
+        let size = groups[0] as usize;
+        if [b'#', b'?'].contains(&springs[0]) {
+            // only valid if:
+            // - there are enough springs left
+            // - the required block size can be achived (no '.' in first n springs)
+            // - spring after block must be '.' (either no springs left or next spring is not '#')
+            if size <= springs.len()
+                && !springs[..size].contains(&b'.')
+                && (size == springs.len() || springs[size] != b'#')
+            {
+                // the block is valid -> remove n+1 springs (because after group there must be '.') and the first group size from groups
+                let rest = springs.get(size + 1..).unwrap_or_default();
+                result += count(memo, rest, &groups[1..]);
             }
         }
-    }
 
-    result
+        result
+    })
+}
+
+fn arrangements(springs: &str, groups: &[u32]) -> u64 {
+    count(&mut Memo::new(), springs.as_bytes(), groups)
 }
 
 fn task_1(input: &str) -> u64 {
@@ -68,7 +70,7 @@ fn task_1(input: &str) -> u64 {
             let (springs, nums): (&str, &str) = line.split(" ").collect_tuple().unwrap();
             let group_sizes: Vec<u32> = nums.split(",").map(|n| n.parse().unwrap()).collect_vec();
 
-            count(springs.to_string(), group_sizes)
+            arrangements(springs, &group_sizes)
         })
         .sum()
 }
@@ -82,13 +84,50 @@ fn task_2(input: &str) -> u64 {
             let springs = [springs; 5].join("?");
             let group_sizes = iter::repeat(group_sizes).take(5).flatten().collect_vec();
 
-            count(springs, group_sizes) as u64
+            arrangements(&springs, &group_sizes)
         })
         .sum()
 }
 
+fn stats(input: &str) -> Stats {
+    let rows = input
+        .lines()
+        .map(|line| line.split_once(' ').unwrap())
+        .collect_vec();
+    let longest_unknown_run = rows
+        .iter()
+        .flat_map(|(springs, _)| springs.split(|c| c != '?'))
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let lengths = rows.iter().map(|(springs, _)| springs.len()).collect_vec();
+    let groups = rows
+        .iter()
+        .map(|(_, groups)| groups.split(',').count())
+        .collect_vec();
+
+    let mut stats = Stats::new();
+    stats
+        .add("rows", rows.len())
+        .add("springs per row", stats::span(&lengths))
+        .add("longest run of ?", longest_unknown_run)
+        .add("groups per row", stats::span(&groups));
+    stats
+}
+
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    if std::env::args().any(|arg| arg == "--stats") {
+        stats(input).print();
+        return;
+    }
 
     println!("task 1: {}", task_1(input));
     println!("task 2: {}", task_2(input));
@@ -98,29 +137,20 @@ fn main() {
 mod test {
     use super::*;
 
-    #[test]
-    fn task_1_works() {
-        let input = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
-
-        let result = task_1(input);
-        assert_eq!(result, 21);
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
     }
 
     #[test]
-    fn task_2_works() {
-        let input = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
-
-        let result = task_2(input);
-        assert_eq!(result, 525152);
+    fn stats_works() {
+        let stats = stats(include_str!("../examples/example.txt"));
+
+        let values: Vec<&str> = stats.0.iter().map(|(_, value)| value.as_str()).collect();
+        assert_eq!(values, vec!["6", "7..=19", "8", "3..=4"]);
+
+        let empty = super::stats("");
+        let values: Vec<&str> = empty.0.iter().map(|(_, value)| value.as_str()).collect();
+        assert_eq!(values, vec!["0", "-", "0", "-"]);
     }
 }
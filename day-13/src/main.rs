@@ -1,210 +1,127 @@
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::one_of,
-    multi::{many1, separated_list1},
-    IResult,
+use nom::IResult;
+use shared::{
+    explain::Explained,
+    grid::{Grid, Transform, View},
+    input::normalize,
+    parse::{blocks, grid},
 };
-use simple_matrix::Matrix;
 
-fn parse_mat(input: &str) -> IResult<&str, Matrix<char>> {
-    let (input, lines) = separated_list1(tag("\n"), many1(one_of(&['.', '#'][..])))(input)?;
+fn parse_mat(input: &str) -> IResult<&str, Grid<char>> {
+    let (input, lines) = grid(|c| ['.', '#'].contains(&c).then_some(c))(input)?;
 
-    Ok((
-        input,
-        Matrix::from_iter(lines.len(), lines[0].len(), lines.iter().flatten().copied()),
-    ))
+    Ok((input, Grid::from_rows(lines)))
 }
 
-fn parse_mats(input: &str) -> IResult<&str, Vec<Matrix<char>>> {
-    let (input, mats) = separated_list1(tag("\n\n"), parse_mat)(input)?;
+fn parse_mats(input: &str) -> IResult<&str, Vec<Grid<char>>> {
+    let (input, mats) = blocks(parse_mat)(input)?;
 
     Ok((input, mats))
 }
 
-fn task_1(input: &str) -> u32 {
-    let matrices = parse_mats(input).unwrap().1;
-
-    matrices
-        .iter()
-        .map(|mat| {
-            let mut res = 0;
-
-            for (l, r) in (0..mat.cols()).tuple_windows() {
-                let mut left = mat.get_col(l).unwrap().collect_vec();
-                let mut right = mat.get_col(r).unwrap().collect_vec();
-
-                let mut i = 1;
-                while left == right {
-                    if i > l {
-                        res = (l + 1) as u32;
-                        break;
-                    }
-                    if let Some(l) = mat.get_col(l - i) {
-                        left = l.collect_vec();
-                    } else {
-                        if l - i == 0 {
-                            res = (l + 1) as u32;
-                        }
-                        break;
-                    }
-                    if let Some(r) = mat.get_col(r + i) {
-                        right = r.collect_vec();
-                    } else {
-                        if r + i == mat.cols() {
-                            res = (l + 1) as u32;
-                        }
-                        break;
-                    }
-                    i += 1;
-                }
-            }
+// the row a pattern is mirrored below, if the rows reflected onto each other differ in exactly `smudges` cells
+fn mirror_row(mat: View<char>, smudges: usize) -> Option<usize> {
+    (1..mat.height()).find(|&below| {
+        let differences: usize = (0..below.min(mat.height() - below))
+            .map(|i| {
+                mat.row(below - 1 - i)
+                    .zip(mat.row(below + i))
+                    .filter(|(a, b)| a != b)
+                    .count()
+            })
+            .sum();
+
+        differences == smudges
+    })
+}
 
-            for (t, b) in (0..mat.rows()).tuple_windows() {
-                let mut top = mat.get_row(t).unwrap().collect_vec();
-                let mut bottom = mat.get_row(b).unwrap().collect_vec();
+// where a pattern is mirrored: below a row or right of a column, counted from 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Axis {
+    Row(usize),
+    Column(usize),
+}
 
-                let mut i = 1;
-                while top == bottom {
-                    if i > t {
-                        res = ((t + 1) * 100) as u32;
-                        break;
-                    }
-                    if let Some(t) = mat.get_row(t - i) {
-                        top = t.collect_vec();
-                    } else {
-                        if t - i == 0 {
-                            res = ((t + 1) * 100) as u32;
-                        }
-                        break;
-                    }
-                    if let Some(b) = mat.get_row(b + i) {
-                        bottom = b.collect_vec();
-                    } else {
-                        if b + i == mat.rows() {
-                            res = ((t + 1) * 100) as u32;
-                        }
-                        break;
-                    }
-                    i += 1;
-                }
-            }
+impl Axis {
+    fn summary(self) -> u32 {
+        match self {
+            Axis::Row(row) => (row * 100) as u32,
+            Axis::Column(column) => column as u32,
+        }
+    }
+}
 
-            res
-        })
-        .sum()
+fn describe(pattern: usize, axis: Option<Axis>) -> String {
+    match axis {
+        Some(Axis::Row(row)) => format!(
+            "pattern {}: mirrored below row {row} -> {}",
+            pattern + 1,
+            row * 100
+        ),
+        Some(Axis::Column(column)) => format!(
+            "pattern {}: mirrored right of column {column} -> {column}",
+            pattern + 1
+        ),
+        None => format!("pattern {}: no reflection found -> 0", pattern + 1),
+    }
 }
 
-fn task_2(input: &str) -> u32 {
+fn solve(input: &str, smudges: usize, mut steps: Option<&mut Vec<String>>) -> u32 {
     let matrices = parse_mats(input).unwrap().1;
 
     matrices
         .iter()
-        .map(|mat| {
-            let mut res = 0;
-
-            for (l, r) in (0..mat.cols()).tuple_windows() {
-                let mut left = mat.get_col(l).unwrap().collect_vec();
-                let mut right = mat.get_col(r).unwrap().collect_vec();
-
-                let mut changed_char = false;
-
-                let mut i = 1;
-                while left == right || (left
-                        .iter()
-                        .zip(right.clone())
-                        .filter(|(a, b)| *a != b)
-                        .count()
-                        == 1
-                        && changed_char == false) {
-                    if left != right {
-                        changed_char = true;
-                    }
-
-                    if i > l {
-                        if changed_char {
-                            res = (l + 1) as u32;
-                        }
-                        break;
-                    }
-                    if let Some(l) = mat.get_col(l - i) {
-                        left = l.collect_vec();
-                    } else {
-                        if l - i == 0 && changed_char {
-                            res = (l + 1) as u32;
-                        }
-                        break;
-                    }
-                    if let Some(r) = mat.get_col(r + i) {
-                        right = r.collect_vec();
-                    } else {
-                        if r + i == mat.cols() && changed_char  {
-                            res = (l + 1) as u32;
-                        }
-                        break;
-                    }
-                    i += 1;
-                }
+        .enumerate()
+        .map(|(pattern, mat)| {
+            // the columns of a pattern are the rows of its transpose
+            let axis = mirror_row(mat.view(Transform::Identity), smudges)
+                .map(Axis::Row)
+                .or_else(|| mirror_row(mat.view(Transform::Transpose), smudges).map(Axis::Column));
+
+            if let Some(steps) = &mut steps {
+                steps.push(describe(pattern, axis));
             }
 
-            for (t, b) in (0..mat.rows()).tuple_windows() {
-                let mut top = mat.get_row(t).unwrap().collect_vec();
-                let mut bottom = mat.get_row(b).unwrap().collect_vec();
-
-                let mut changed_char = false;
+            axis.map_or(0, Axis::summary)
+        })
+        .sum()
+}
 
-                let mut i = 1;
-                while top == bottom
-                    || (top
-                        .iter()
-                        .zip(bottom.clone())
-                        .filter(|(a, b)| *a != b)
-                        .count()
-                        == 1
-                        && changed_char == false)
-                {
-                    if top != bottom {
-                        changed_char = true;
-                    }
+fn explain_1(input: &str) -> Explained<u32> {
+    Explained::record(|steps| solve(input, 0, Some(steps)))
+}
 
-                    if i > t {
-                        if changed_char {
-                            res = ((t + 1) * 100) as u32;
-                        }
-                        break;
-                    }
-                    if let Some(t) = mat.get_row(t - i) {
-                        top = t.collect_vec();
-                    } else {
-                        if t - i == 0 && changed_char {
-                            res = ((t + 1) * 100) as u32;
-                        }
-                        break;
-                    }
-                    if let Some(b) = mat.get_row(b + i) {
-                        bottom = b.collect_vec();
-                    } else {
-                        if b + i == mat.rows() && changed_char {
-                            res = ((t + 1) * 100) as u32;
-                        }
-                        break;
-                    }
-                    i += 1;
-                }
-            }
+fn task_1(input: &str) -> u32 {
+    solve(input, 0, None)
+}
 
-            // if res == 0 {
-            //     panic!("res should be > 0");
-            // }
-            dbg!(res);
+// the reflection is only found once exactly one smudge is fixed
+fn explain_2(input: &str) -> Explained<u32> {
+    Explained::record(|steps| solve(input, 1, Some(steps)))
+}
 
-            res
-        })
-        .sum()
+fn task_2(input: &str) -> u32 {
+    solve(input, 1, None)
 }
 
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    if std::env::args().any(|arg| arg == "--stats") {
+        shared::stats::grids(input).print();
+        return;
+    }
+
+    if std::env::args().any(|arg| arg == "--explain") {
+        explain_1(input).print("task 1");
+        explain_2(input).print("task 2");
+        return;
+    }
 
     println!("task 1: {}", task_1(input));
     println!("task 2: {}", task_2(input));
@@ -214,30 +131,13 @@ fn main() {
 mod test {
     use super::*;
 
-    #[test]
-    fn task_1_works() {
-        let input = "#.##..##.
-..#.##.#.
-##......#
-##......#
-..#.##.#.
-..##..##.
-#.#.##.#.
-
-#...##..#
-#....#..#
-..##..###
-#####.##.
-#####.##.
-..##..###
-#....#..#";
-
-        let result = task_1(input);
-        assert_eq!(result, 405); 
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
     }
 
     #[test]
-    fn task_2_works() {
+    fn explain_works() {
         let input = "#.##..##.
 ..#.##.#.
 ##......#
@@ -254,7 +154,19 @@ mod test {
 ..##..###
 #....#..#";
 
-        let result = task_2(input);
-        assert_eq!(result, 400);
+        assert_eq!(
+            explain_1(input).steps,
+            vec![
+                "pattern 1: mirrored right of column 5 -> 5",
+                "pattern 2: mirrored below row 4 -> 400",
+            ]
+        );
+        assert_eq!(
+            explain_2(input).steps,
+            vec![
+                "pattern 1: mirrored below row 3 -> 300",
+                "pattern 2: mirrored below row 1 -> 100",
+            ]
+        );
     }
 }
@@ -1,15 +1,21 @@
-use itertools::Itertools;
-use simple_matrix::Matrix;
+use shared::{
+    grid::{Grid, Transform},
+    input::normalize,
+};
 
-fn move_stones(mat: &mut Matrix<char>) {
-    for i in 0..mat.cols() {
-        for j in 1..mat.rows() {
-            if *mat.get(j, i).unwrap() == 'O' {
+fn parse_platform(input: &str) -> Grid<char> {
+    Grid::from_rows(input.lines().map(|line| line.chars().collect()).collect())
+}
+
+fn move_stones(mat: &mut Grid<char>) {
+    for i in 0..mat.width() {
+        for j in 1..mat.height() {
+            if *mat.get(i, j).unwrap() == 'O' {
                 let mut j = j;
-                let char = mat.get_mut(j, i).unwrap();
+                let char = mat.get_mut(i, j).unwrap();
                 *char = '.';
 
-                while let Some('.') = mat.get(j - 1, i) {
+                while let Some('.') = mat.get(i, j - 1) {
                     if j == 1 {
                         j = 0;
                         break;
@@ -17,93 +23,71 @@ fn move_stones(mat: &mut Matrix<char>) {
                     j -= 1;
                 }
 
-                let char = mat.get_mut(j, i).unwrap();
+                let char = mat.get_mut(i, j).unwrap();
                 *char = 'O';
             }
         }
     }
 }
 
+// every stone weighs as many rows as there are from it to the south edge
+fn load(mat: &Grid<char>) -> usize {
+    mat.rows()
+        .enumerate()
+        .map(|(j, row)| (mat.height() - j) * row.iter().filter(|char| **char == 'O').count())
+        .sum()
+}
+
 fn task_1(input: &str) -> u32 {
-    let mut mat: Matrix<char> = Matrix::from_iter(
-        input.lines().count(),
-        input.lines().next().unwrap().chars().count(),
-        input.chars().filter(|c| ['.', 'O', '#'].contains(c)),
-    );
+    let mut mat = parse_platform(input);
 
     move_stones(&mut mat);
 
-    let mut res = 0;
-    for i in (0..mat.rows()).rev() {
-        let row = mat.get_row(mat.rows() - 1 - i).unwrap();
-        res += (i + 1) * row.filter(|char| **char == 'O').count()
-    }
-
-    res as u32
+    load(&mat) as u32
 }
 
-fn rotate_mat(mat: &mut Matrix<char>) {
-    // transpose
-    for i in 0..mat.rows() {
-        for j in 0..mat.cols() {
-            if j > i {
-                break;
-            }
-
-            let tmp1 = mat.get(i, j).copied().unwrap();
-            let tmp2 = mat.get(j, i).copied().unwrap();
-
-            let el1 = mat.get_mut(i, j).unwrap();
-            *el1 = tmp2;
-
-            let el2 = mat.get_mut(j, i).unwrap();
-            *el2 = tmp1;
-        }
-    }
-
-    // reverse rows
-    for i in 0..mat.rows() {
-        let row = mat.get_row(i).unwrap().copied().collect_vec();
-        for (j, char) in row.iter().rev().enumerate() {
-            let el = mat.get_mut(i, j).unwrap();
-            *el = *char;
-        }
+// tilts north, west, south and east, by tilting north and turning the
+// platform so the next direction of the cycle is north
+fn spin_cycle(mut mat: Grid<char>) -> Grid<char> {
+    for _ in 0..4 {
+        move_stones(&mut mat);
+        mat = mat.transformed(Transform::Rotate90);
     }
+    mat
 }
 
 fn task_2(input: &str) -> u32 {
-    let mut mat: Matrix<char> = Matrix::from_iter(
-        input.lines().count(),
-        input.lines().next().unwrap().chars().count(),
-        input.chars().filter(|c| ['.', 'O', '#'].contains(c)),
-    );
+    let mut mat = parse_platform(input);
 
-    let mut seen_states: Vec<Matrix<char>> = vec![mat.clone()];
+    let mut seen_states: Vec<Grid<char>> = vec![mat.clone()];
 
     loop {
-        for _ in 0..4 {
-            move_stones(&mut mat);
-            rotate_mat(&mut mat);
-        }
+        mat = spin_cycle(mat);
         if let Some(index) = seen_states.iter().position(|x| x == &mat) {
             let cycle_length = seen_states.len() - index;
             let cycle_start = index;
             let final_mat =
-                seen_states[cycle_start + (1_000_000_000 - cycle_start) % cycle_length].clone();
-
-            let mut res = 0;
-            for i in (0..final_mat.rows()).rev() {
-                let row = final_mat.get_row(final_mat.rows() - 1 - i).unwrap();
-                res += (i + 1) * row.filter(|char| **char == 'O').count()
-            } 
-            return res as u32;
+                &seen_states[cycle_start + (1_000_000_000 - cycle_start) % cycle_length];
+
+            return load(final_mat) as u32;
         }
         seen_states.push(mat.clone());
     }
 }
 
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    if std::env::args().any(|arg| arg == "--stats") {
+        shared::stats::grids(input).print();
+        return;
+    }
 
     println!("task 1: {}", task_1(input));
     println!("task 2: {}", task_2(input));
@@ -113,37 +97,19 @@ fn main() {
 mod test {
     use super::*;
 
-    #[test]
-    fn task_1_works() {
-        let input = "O....#....
-O.OO#....#
-.....##...
-OO.#O....O
-.O.....O#.
-O.#..O.#.#
-..O..#O..O
-.......O..
-#....###..
-#OO..#....";
-
-        let result = task_1(input);
-        assert_eq!(result, 136);
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
     }
 
     #[test]
-    fn task_2_works() {
-        let input = "O....#....
-O.OO#....#
-.....##...
-OO.#O....O
-.O.....O#.
-O.#..O.#.#
-..O..#O..O
-.......O..
-#....###..
-#OO..#....";
-
-        let result = task_2(input);
-        assert_eq!(result, 64);
+    fn rectangular_platform_works() {
+        let mut mat = parse_platform("O.#O.\n.O..O\nO#.O.");
+
+        move_stones(&mut mat);
+        assert_eq!(mat, parse_platform("OO#OO\nO..O.\n.#..."));
+
+        let mat = spin_cycle(parse_platform("O.#O.\n.O..O\nO#.O."));
+        assert_eq!(mat, parse_platform(".O#..\n...OO\nO#.OO"));
     }
 }
@@ -1,4 +1,5 @@
 use itertools::Itertools;
+use shared::input::normalize;
 
 fn hash(str: &str) -> u32 {
     str.chars()
@@ -54,7 +55,13 @@ fn task_2(input: &str) -> u32 {
 }
 
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let parts = [
+        shared::batch::Part::new("task 1", |input| task_1(input).to_string()),
+        shared::batch::Part::new("task 2", |input| task_2(input).to_string()),
+    ];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
 
     println!("task 1: {}", task_1(input));
     println!("task 2: {}", task_2(input));
@@ -64,19 +71,8 @@ fn main() {
 mod test {
     use super::*;
 
-    #[test]
-    fn task_1_works() {
-        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-
-        let result = task_1(input);
-        assert_eq!(result, 1320);
-    }
-
-    #[test]
-    fn task_2_works() {
-        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-
-        let result = task_2(input);
-        assert_eq!(result, 145);
+    shared::example_tests! {
+        part_1 => task_1,
+        part_2 => task_2,
     }
 }
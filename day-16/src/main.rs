@@ -1,140 +1,64 @@
-use std::{fmt::Display, collections::HashMap, char};
+use std::collections::HashSet;
 
-use simple_matrix::Matrix;
+use itertools::Itertools;
+use shared::{
+    geometry::{Bounds, Dir, Mirror, Pos},
+    input::normalize,
+    parse::grid,
+};
 
-fn print_mat<T: Display>(mat: &Matrix<T>) {
-    println!("{}x{}", mat.rows(), mat.cols());
-    for i in 0..mat.rows() {
-        for j in 0..mat.cols() {
-            print!("{} ", mat.get(i, j).unwrap());
-        }
-        println!("");
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Dir {
-    Horizontal,
-    Vertical
+fn parse_contraption(input: &str) -> Vec<Vec<char>> {
+    grid(|char| "./\\|-".contains(char).then_some(char))(input)
+        .unwrap()
+        .1
 }
 
-fn neighborhood(mat: &Matrix<char>, p: (usize, usize)) -> Vec<Option<char>> {
-    let mut n: Vec<Option<char>> = vec![];
-    if p.0 > 0 {
-        n.push(mat.get(p.0-1, p.1).copied())
-    } else {
-        n.push(None);
+// directions a beam heading in `d` leaves `tile` in
+fn deflect(tile: char, d: Dir) -> Vec<Dir> {
+    match tile {
+        '/' => vec![d.reflect(Mirror::Slash)],
+        '\\' => vec![d.reflect(Mirror::Backslash)],
+        '|' if d.is_horizontal() => vec![Dir::North, Dir::South],
+        '-' if !d.is_horizontal() => vec![Dir::East, Dir::West],
+        _ => vec![d],
     }
-    n.push(mat.get(p.0, p.1+1).copied());
-    n.push(mat.get(p.0+1, p.1).copied());
-    if p.1 > 0 {
-        n.push(mat.get(p.0, p.1-1).copied())
-    } else {
-        n.push(None);
-    }
-
-    n
 }
 
-fn activate(mat: &mut Matrix<char>, p: (usize, usize), d: Dir) {
-    if let Some(char) = mat.get_mut(p.0, p.1) {
-        match char {
-            '/' => {
-                let n = neighborhood(mat, p);
-                if let Some(t) = n.get(0).unwrap() {
-                    
-                }
-                if let Some(l) = n.get(3).unwrap() {
-                    
-                }
-            },
-            '\\' => {
-                if let Some('#') = mat.get(p.0-1, p.1) {
-                    activate(mat, (p.0, p.1+1), Dir::Horizontal)
-                } 
-                if let Some('#') = mat.get(p.0, p.1+1) {
-                    activate(mat, (p.0-1, p.1), Dir::Vertical)
-                } 
-                if let Some('#') = mat.get(p.0+1, p.1) {
-                    activate(mat, (p.0, p.1-1), Dir::Horizontal)
-                }
-                if let Some('#') = mat.get(p.0, p.1-1) {
-                    activate(mat, (p.0+1, p.1), Dir::Vertical)
-                }
-            }
-            '|' => {
-                if let Some('#') = mat.get(p.0-1, p.1) {
-                    activate(mat, (p.0+1, p.1), d)
-                } 
-                if let Some('#') = mat.get(p.0, p.1+1) {
-                    activate(mat, (p.0-1, p.1), Dir::Vertical);
-                    activate(mat, (p.0+1, p.1), Dir::Vertical);
-                } 
-                if let Some('#') = mat.get(p.0+1, p.1) {
-                    activate(mat, (p.0-1, p.1), d)
-                }
-                if let Some('#') = mat.get(p.0, p.1-1) {
-                    activate(mat, (p.0-1, p.1), Dir::Vertical);
-                    activate(mat, (p.0+1, p.1), Dir::Vertical);
-                }
-            },
-            '-' => {
-               if let Some('#') = mat.get(p.0-1, p.1) {
-                    activate(mat, (p.0, p.1-1), Dir::Horizontal);
-                    activate(mat, (p.0, p.1+1), Dir::Horizontal);
-                } 
-                if let Some('#') = mat.get(p.0, p.1+1) {
-                    activate(mat, (p.0, p.1-1), d);
-                } 
-                if let Some('#') = mat.get(p.0+1, p.1) {
-                    activate(mat, (p.0, p.1-1), Dir::Horizontal);
-                    activate(mat, (p.0, p.1+1), Dir::Horizontal);
-                }
-                if let Some('#') = mat.get(p.0, p.1-1) {
-                    activate(mat, (p.0, p.1+1), d);
-                } 
-            },
-            '.' | '#' => {
-                if *char == '.' {
-                    *char = '#';
-                }
-                match d {
-                    Dir::Horizontal => {
-                        activate(mat, (p.0-1,p.1), d);
-                        activate(mat, (p.0+1,p.1), d);
-                    },
-                    Dir::Vertical => {
-                        activate(mat, (p.0,p.1-1), d);
-                        activate(mat, (p.0,p.1+1), d);
-                    }
-                }
-            },
-            _ => panic!("invalid char")
+fn energize(contraption: &[Vec<char>], start: Pos, d: Dir) -> usize {
+    let bounds = Bounds::of_grid(contraption[0].len(), contraption.len());
+    let mut seen: HashSet<(Pos, Dir)> = HashSet::new();
+    let mut beams = vec![(start, d)];
+
+    while let Some((p, d)) = beams.pop() {
+        if !bounds.contains(p) || !seen.insert((p, d)) {
+            continue;
+        }
+        for next in deflect(contraption[p.y as usize][p.x as usize], d) {
+            beams.push((p.step(next), next));
         }
     }
+
+    seen.iter().map(|(p, _)| p).unique().count()
 }
 
 fn task_1(input: &str) -> u32 {
-    let mut mat: Matrix<char> = Matrix::from_iter(
-        input.lines().count(),
-        input
-            .lines()
-            .next()
-            .unwrap()
-            .chars()
-            .filter(|char| *char != '\n')
-            .count(),
-        input.chars().filter(|char| *char != '\n'),
-    );
-    activate(&mut mat, (0,0), Dir::Horizontal);
+    let contraption = parse_contraption(input);
 
-    print_mat(&mat);
-
-    todo!()
+    energize(&contraption, Pos::new(0, 0), Dir::East) as u32
 }
 
 fn main() {
-    let input = include_str!("./data.txt");
+    let input = &normalize(include_str!("./data.txt"));
+
+    let parts = [shared::batch::Part::new("task 1", |input| {
+        task_1(input).to_string()
+    })];
+    shared::batch::dispatch(env!("CARGO_MANIFEST_DIR"), input, &parts);
+
+    if std::env::args().any(|arg| arg == "--stats") {
+        shared::stats::grids(input).print();
+        return;
+    }
 
     println!("task 1: {}", task_1(input));
 }
@@ -143,20 +67,7 @@ fn main() {
 mod test {
     use super::*;
 
-    #[test]
-    fn task_1_works() {
-        let input = r#".|...\....
-|.-.\.....
-.....|-...
-........|.
-..........
-.........\
-..../.\\..
-.-.-/..|..
-.|....-|.\
-..//.|...."#;
-
-        let result = task_1(input);
-        assert_eq!(result, 46);
+    shared::example_tests! {
+        part_1 => task_1,
     }
 }
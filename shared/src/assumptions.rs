@@ -0,0 +1,51 @@
+/// A property of the puzzle input that a solution relies on without
+/// checking it while solving.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assumption {
+    pub description: &'static str,
+    pub violations: Vec<String>,
+}
+
+impl Assumption {
+    pub fn new(description: &'static str, violations: Vec<String>) -> Self {
+        Assumption {
+            description,
+            violations,
+        }
+    }
+
+    pub fn holds(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Prints every assumption with its violations and returns whether all of
+/// them hold.
+pub fn report(assumptions: &[Assumption]) -> bool {
+    for assumption in assumptions {
+        if assumption.holds() {
+            println!("ok      {}", assumption.description);
+        } else {
+            println!("FAILED  {}", assumption.description);
+            for violation in assumption.violations.iter() {
+                println!("        - {violation}");
+            }
+        }
+    }
+
+    assumptions.iter().all(Assumption::holds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_works() {
+        let ok = Assumption::new("holds", vec![]);
+        let failed = Assumption::new("does not hold", vec!["line 1".to_string()]);
+
+        assert!(report(&[ok.clone(), ok.clone()]));
+        assert!(!report(&[ok, failed]));
+    }
+}
@@ -0,0 +1,431 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    fs,
+    panic::{self, AssertUnwindSafe, PanicHookInfo},
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{calendar, dashboard, input::normalize, perf, report, watch};
+
+/// Extension of the optional file next to an input holding its expected
+/// answers, in the same `task N: answer` lines `main` prints. Running
+/// `--batch <dir> --save` writes one for every input from its current
+/// answers.
+pub const ANSWERS: &str = "answers";
+
+/// A part is flagged as slow on an input when it takes this many times the
+/// median of that part over the other inputs.
+const OUTLIER_FACTOR: u32 = 3;
+
+/// How long a part may run when `--timeout` isn't given.
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Stack size of the threads parts run on, the same as the main thread's
+/// on Linux, so recursive solvers don't overflow sooner than in `main`.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// A part's label as `main` prints it, and its solver with the answer
+/// turned into a string. Solvers run on a thread of their own with
+/// `--batch`, so they can be timed out.
+pub struct Part {
+    pub label: &'static str,
+    pub solve: Arc<dyn Fn(&str) -> String + Send + Sync>,
+}
+
+impl Part {
+    pub fn new(
+        label: &'static str,
+        solve: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        Part {
+            label,
+            solve: Arc::new(solve),
+        }
+    }
+}
+
+/// The directory passed with `--batch`, if the flag was given. Exits if no
+/// directory follows it.
+pub fn requested() -> Option<PathBuf> {
+    if !std::env::args().any(|arg| arg == "--batch") {
+        return None;
+    }
+
+    match std::env::args().skip_while(|arg| arg != "--batch").nth(1) {
+        Some(dir) if !dir.starts_with("--") => Some(PathBuf::from(dir)),
+        _ => {
+            eprintln!("--batch needs a directory of inputs");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// How long a part, or with `--calendar` a whole day, may run before it
+/// counts as timed out: the seconds passed with `--timeout`, or a minute.
+/// Exits if no number of seconds follows the flag.
+pub fn timeout() -> Duration {
+    if !std::env::args().any(|arg| arg == "--timeout") {
+        return TIMEOUT;
+    }
+
+    match std::env::args()
+        .skip_while(|arg| arg != "--timeout")
+        .nth(1)
+        .and_then(|secs| secs.parse().ok())
+    {
+        Some(secs) => Duration::from_secs_f64(secs),
+        None => {
+            eprintln!("--timeout needs a number of seconds");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Handles the flags every day supports besides its own, `--batch <dir>`,
+/// `--perf`, `--watch`, `--calendar`, `--dashboard` and `--report`, and
+/// exits if one of them was given. `dir` is the day's crate directory.
+pub fn dispatch(dir: impl AsRef<Path>, input: &str, parts: &[Part]) {
+    if let Some(inputs) = requested() {
+        let ok = run(&inputs, parts);
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
+    if std::env::args().any(|arg| arg == "--perf") {
+        let ok = perf::run(dir.as_ref(), input, parts);
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
+    if std::env::args().any(|arg| arg == "--watch") {
+        watch::run(dir.as_ref());
+    }
+
+    if std::env::args().any(|arg| arg == "--calendar") {
+        let ok = calendar::run(dir.as_ref());
+        std::process::exit(if ok { 0 } else { 1 });
+    }
+
+    if std::env::args().any(|arg| arg == "--dashboard") {
+        dashboard::run(dir.as_ref());
+        std::process::exit(0);
+    }
+
+    if std::env::args().any(|arg| arg == "--report") {
+        report::run(dir.as_ref());
+        std::process::exit(0);
+    }
+}
+
+// "task 1: 142" -> ("task 1", "142")
+pub(crate) fn parse_answers(answers: &str) -> Vec<(String, String)> {
+    answers
+        .lines()
+        .filter_map(|l| l.split_once(": "))
+        .map(|(label, answer)| (label.trim().to_string(), answer.trim().to_string()))
+        .collect()
+}
+
+struct Run {
+    // why there is no answer if the part panicked or timed out
+    answer: Result<String, String>,
+    expected: Option<String>,
+    time: Duration,
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "no message".to_string(),
+        },
+    }
+}
+
+thread_local! {
+    // set while a part runs, so its panics are reported with its input
+    // instead of printed
+    static SOLVING: Cell<bool> = const { Cell::new(false) };
+}
+
+type Hook = Arc<Box<dyn Fn(&PanicHookInfo) + Send + Sync>>;
+
+/// Keeps panics in parts quiet while it lives, and puts the panic hook
+/// that was there before back when dropped. Panics on other threads, like
+/// other tests, still go to that hook.
+struct QuietPanics(Option<Hook>);
+
+impl QuietPanics {
+    fn new() -> Self {
+        let previous: Hook = Arc::new(panic::take_hook());
+        let hook = Arc::clone(&previous);
+        panic::set_hook(Box::new(move |info| {
+            if !SOLVING.get() {
+                hook(info);
+            }
+        }));
+        QuietPanics(Some(previous))
+    }
+}
+
+impl Drop for QuietPanics {
+    fn drop(&mut self) {
+        // dropping ours leaves this the only handle on the previous hook,
+        // unless another thread swapped hooks in the meantime
+        drop(panic::take_hook());
+        let previous = self.0.take().unwrap();
+        match Arc::try_unwrap(previous) {
+            Ok(previous) => panic::set_hook(previous),
+            Err(previous) => panic::set_hook(Box::new(move |info| previous(info))),
+        }
+    }
+}
+
+// runs the part on a thread of its own, which is left behind if it times
+// out: threads can't be killed, but the process exits after the batch
+fn solve(part: &Part, input: &Arc<str>, timeout: Duration) -> Result<String, String> {
+    let (sender, receiver) = mpsc::channel();
+    let solve = Arc::clone(&part.solve);
+    let input = Arc::clone(input);
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            SOLVING.set(true);
+            let answer = panic::catch_unwind(AssertUnwindSafe(|| solve(&input)));
+            // nobody is listening anymore if the part timed out
+            let _ = sender.send(answer.map_err(|e| format!("panicked, {}", panic_message(e))));
+        })
+        .unwrap_or_else(|e| panic!("could not start a thread: {e}"));
+
+    receiver
+        .recv_timeout(timeout)
+        .unwrap_or_else(|_| Err(format!("timed out after {timeout:.2?}")))
+}
+
+pub(crate) fn sidecar(file: &Path) -> PathBuf {
+    let mut sidecar = file.to_path_buf().into_os_string();
+    sidecar.push(format!(".{ANSWERS}"));
+    sidecar.into()
+}
+
+// the median of `times` without the one at `skip`, or None if there is no other
+fn median_of_others(times: &[Duration], skip: usize) -> Option<Duration> {
+    let mut others: Vec<Duration> = times
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != skip)
+        .map(|(_, time)| *time)
+        .collect();
+    others.sort();
+
+    others.get(others.len() / 2).copied()
+}
+
+/// Runs every part in `parts` on every input in `dir`, prints the answers
+/// with their timings, and returns whether all of them match their
+/// `.answers` file. Inputs without one are only timed. An input that can't
+/// be read, or a part that panics or times out on one, fails the batch but
+/// the other inputs still run. A `dir` without inputs fails it too. With
+/// `--save`, the answers are written to the `.answers` files instead of
+/// compared with them.
+pub fn run(dir: &Path, parts: &[Part]) -> bool {
+    let save = std::env::args().any(|arg| arg == "--save");
+    run_inputs(dir, parts, save, timeout())
+}
+
+fn run_inputs(dir: &Path, parts: &[Part], save: bool, timeout: Duration) -> bool {
+    let mut files: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| path.is_file() && path.extension().is_none_or(|e| e != ANSWERS))
+            .collect(),
+        Err(e) => {
+            eprintln!("could not read {}: {e}", dir.display());
+            return false;
+        }
+    };
+    files.sort();
+
+    if files.is_empty() {
+        eprintln!("no inputs found in {}", dir.display());
+        return false;
+    }
+
+    let quiet = QuietPanics::new();
+    let runs: Vec<Result<Vec<Run>, String>> = files
+        .iter()
+        .map(|file| {
+            let input: Arc<str> = fs::read_to_string(file)
+                .map(|input| normalize(&input).into())
+                .map_err(|e| format!("could not read it: {e}"))?;
+            let answers = fs::read_to_string(sidecar(file))
+                .map(|a| parse_answers(&a))
+                .unwrap_or_default();
+
+            Ok(parts
+                .iter()
+                .map(|part| {
+                    let start = Instant::now();
+                    let answer = solve(part, &input, timeout);
+                    let time = start.elapsed();
+                    let expected = answers
+                        .iter()
+                        .find(|(l, _)| l == part.label)
+                        .map(|(_, a)| a.clone());
+
+                    Run {
+                        answer,
+                        expected,
+                        time,
+                    }
+                })
+                .collect())
+        })
+        .collect();
+    drop(quiet);
+
+    // the times of each part on every input that could be read
+    let times: Vec<Vec<Duration>> = (0..parts.len())
+        .map(|i| runs.iter().flatten().map(|r| r[i].time).collect())
+        .collect();
+
+    let mut ok = true;
+    let mut read = 0;
+    for (file, runs) in files.iter().zip(runs.iter()) {
+        println!("{}", file.display());
+        let runs = match runs {
+            Ok(runs) => runs,
+            Err(message) => {
+                ok = false;
+                println!("  {message}");
+                continue;
+            }
+        };
+
+        for (part, (run, times)) in parts.iter().zip(runs.iter().zip(times.iter())) {
+            let answer = match &run.answer {
+                Ok(answer) => answer.clone(),
+                Err(message) => {
+                    ok = false;
+                    message.clone()
+                }
+            };
+            let verdict = match (&run.expected, &run.answer) {
+                (None, _) | (_, Err(_)) => String::new(),
+                _ if save => String::new(),
+                (Some(expected), Ok(answer)) if expected == answer => "  ok".to_string(),
+                (Some(expected), Ok(_)) => {
+                    ok = false;
+                    format!("  MISMATCH, expected {expected}")
+                }
+            };
+            let slow = match median_of_others(times, read) {
+                Some(median) if run.time > median * OUTLIER_FACTOR => {
+                    format!("  SLOW, median of the others {median:.2?}")
+                }
+                _ => String::new(),
+            };
+
+            println!(
+                "  {}: {answer} ({:.2?}){verdict}{slow}",
+                part.label, run.time
+            );
+        }
+        read += 1;
+
+        if save {
+            let answers: String = parts
+                .iter()
+                .zip(runs.iter())
+                .filter_map(|(part, run)| {
+                    Some(format!("{}: {}\n", part.label, run.answer.as_ref().ok()?))
+                })
+                .collect();
+            fs::write(sidecar(file), answers)
+                .unwrap_or_else(|e| panic!("could not write {}: {e}", sidecar(file).display()));
+        }
+    }
+
+    ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers_works() {
+        assert_eq!(
+            parse_answers("task 1: 142\ntask 2: 281\n"),
+            vec![
+                ("task 1".to_string(), "142".to_string()),
+                ("task 2".to_string(), "281".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_dir_works() {
+        let dir = std::env::temp_dir().join(format!("shared-batch-empty-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let len = [Part::new("task 1", |input| input.len().to_string())];
+        assert!(!run_inputs(&dir, &len, false, TIMEOUT));
+        assert!(!run_inputs(&dir.join("missing"), &len, false, TIMEOUT));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn median_of_others_works() {
+        let times = [10, 40].map(Duration::from_millis);
+
+        // with two inputs, each is judged against the other alone
+        assert_eq!(median_of_others(&times, 1), Some(times[0]));
+        assert_eq!(median_of_others(&times[..1], 0), None);
+    }
+
+    #[test]
+    fn run_works() {
+        let dir = std::env::temp_dir().join(format!("shared-batch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("alice.txt"), "abc\n").unwrap();
+        fs::write(dir.join("alice.txt.answers"), "task 1: 3\n").unwrap();
+        fs::write(dir.join("bob.txt"), "abcd\n").unwrap();
+
+        let len = [Part::new("task 1", |input| input.len().to_string())];
+        assert!(run_inputs(&dir, &len, false, TIMEOUT));
+
+        fs::write(dir.join("bob.txt.answers"), "task 1: 5\n").unwrap();
+        assert!(!run_inputs(&dir, &len, false, TIMEOUT));
+
+        assert!(run_inputs(&dir, &len, true, TIMEOUT));
+        assert_eq!(
+            fs::read_to_string(dir.join("bob.txt.answers")).unwrap(),
+            "task 1: 4\n"
+        );
+        assert!(run_inputs(&dir, &len, false, TIMEOUT));
+
+        // alice's input panics, bob's still runs
+        let first = [Part::new("task 1", |input| input[3..4].to_string())];
+        assert!(!run_inputs(&dir, &first, false, TIMEOUT));
+
+        // so do the others when one isn't text
+        fs::write(dir.join("carol.txt"), [0xff, 0xfe]).unwrap();
+        assert!(!run_inputs(&dir, &len, false, TIMEOUT));
+        fs::remove_file(dir.join("carol.txt")).unwrap();
+        assert!(run_inputs(&dir, &len, false, TIMEOUT));
+
+        // and when they take too long
+        let slow = [Part::new("task 1", |input| {
+            thread::sleep(Duration::from_millis(500));
+            input.len().to_string()
+        })];
+        assert!(!run_inputs(&dir, &slow, false, Duration::from_millis(50)));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,338 @@
+use std::{
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::batch::{self, parse_answers, sidecar};
+
+/// The year of the `day-NN` crates at the root of the repository. Other
+/// years live in `<year>/day-NN` next to them.
+pub const YEAR: u32 = 2023;
+
+/// How often a running day is checked for having exited, to kill it once
+/// it runs out of time. Its runtime is taken from when its output ends,
+/// so this doesn't round it.
+const POLL: Duration = Duration::from_millis(10);
+
+/// A day's crate, found by [`days`].
+#[derive(Debug, PartialEq)]
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub dir: PathBuf,
+}
+
+/// How running a day's binary on its input went.
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    /// The answers it printed, as `(label, answer)` pairs.
+    Answered(Vec<(String, String)>),
+    /// The message it panicked with.
+    Panicked(String),
+    /// It failed without panicking, with what it printed to stderr.
+    Failed(String),
+    /// It was killed after running this long.
+    TimedOut(Duration),
+}
+
+pub struct Outcome {
+    pub status: Status,
+    /// Everything the binary printed to stdout.
+    pub output: String,
+    pub time: Duration,
+}
+
+impl Day {
+    /// The day's crate and binary name, like `day-07`.
+    pub fn name(&self) -> String {
+        format!("day-{:02}", self.day)
+    }
+
+    /// The day's input, which its binary embeds.
+    pub fn input(&self) -> PathBuf {
+        self.dir.join("src/data.txt")
+    }
+
+    /// The day's answer registry: the expected answers for its input, in
+    /// the batch `.answers` format next to it.
+    pub fn registry(&self) -> PathBuf {
+        sidecar(&self.input())
+    }
+
+    /// The answers in the day's registry, if it has one.
+    pub fn expected(&self) -> Option<Vec<(String, String)>> {
+        fs::read_to_string(self.registry())
+            .ok()
+            .map(|answers| parse_answers(&answers))
+    }
+
+    /// Builds the day in release mode, then runs its binary with `args` and
+    /// times it, killing it if it runs longer than `timeout`. The build
+    /// isn't timed.
+    pub fn run(&self, args: &[&str], timeout: Duration) -> Outcome {
+        let cargo = std::env::var_os("CARGO").unwrap_or("cargo".into());
+        let build = Command::new(cargo)
+            .current_dir(&self.dir)
+            .args(["build", "--release", "-q", "--bin", &self.name()])
+            .output()
+            .unwrap_or_else(|e| panic!("could not run cargo: {e}"));
+        if !build.status.success() {
+            return Outcome {
+                status: Status::Failed(String::from_utf8_lossy(&build.stderr).into_owned()),
+                output: String::new(),
+                time: Duration::ZERO,
+            };
+        }
+
+        let target = std::env::var_os("CARGO_TARGET_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.dir.join("target"));
+        let binary = target.join("release").join(self.name());
+
+        let start = Instant::now();
+        let mut child = Command::new(&binary)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap_or_else(|e| panic!("could not run {}: {e}", binary.display()));
+        let stdout = read_to_end(child.stdout.take().unwrap());
+        let stderr = read_to_end(child.stderr.take().unwrap());
+
+        let exit = loop {
+            match child.try_wait() {
+                Ok(Some(exit)) => break Some(exit),
+                Ok(None) if start.elapsed() < timeout => thread::sleep(POLL),
+                _ => {
+                    // it may have exited since, which is fine
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    break None;
+                }
+            }
+        };
+
+        let (stdout, end) = stdout.join().unwrap();
+        let (stderr, _) = stderr.join().unwrap();
+        let output = String::from_utf8_lossy(&stdout).into_owned();
+        let stderr = String::from_utf8_lossy(&stderr);
+
+        let Some(exit) = exit else {
+            return Outcome {
+                status: Status::TimedOut(timeout),
+                output,
+                time: timeout,
+            };
+        };
+        let time = end - start;
+        let status = if exit.success() {
+            Status::Answered(parse_answers(&output))
+        } else if let Some(message) = panic_message(&stderr) {
+            Status::Panicked(message)
+        } else {
+            Status::Failed(stderr.into_owned())
+        };
+
+        Outcome {
+            status,
+            output,
+            time,
+        }
+    }
+}
+
+// reads all of `pipe` on a thread of its own, so a day printing a lot can't
+// fill the pipe and stall, and notes when it ended
+fn read_to_end(mut pipe: impl Read + Send + 'static) -> JoinHandle<(Vec<u8>, Instant)> {
+    thread::spawn(move || {
+        let mut read = vec![];
+        // a read error ends the output like its end does
+        let _ = pipe.read_to_end(&mut read);
+        (read, Instant::now())
+    })
+}
+
+/// The root of the repository holding the day crate in `dir`.
+pub fn root(dir: &Path) -> PathBuf {
+    let parent = dir.parent().unwrap_or(dir);
+    match year(parent) {
+        Some(_) => parent.parent().unwrap_or(parent).to_path_buf(),
+        None => parent.to_path_buf(),
+    }
+}
+
+// "2022" -> 2022, for the directories holding other years
+fn year(dir: &Path) -> Option<u32> {
+    let name = dir.file_name()?.to_str()?;
+    if name.len() != 4 {
+        return None;
+    }
+    name.parse().ok()
+}
+
+// "day-07" -> 7
+fn day(dir: &Path) -> Option<u32> {
+    dir.file_name()?
+        .to_str()?
+        .strip_prefix("day-")?
+        .parse()
+        .ok()
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Every day crate under `root`, by year and day: the `day-NN` directories
+/// at the root are [`YEAR`]'s, those in a `<year>` directory that year's.
+pub fn days(root: &Path) -> Vec<Day> {
+    let mut days = vec![];
+    for dir in subdirs(root) {
+        if let Some(day) = day(&dir) {
+            days.push(Day {
+                year: YEAR,
+                day,
+                dir,
+            });
+        } else if let Some(year) = year(&dir) {
+            days.extend(subdirs(&dir).into_iter().filter_map(|dir| {
+                Some(Day {
+                    year,
+                    day: day(&dir)?,
+                    dir,
+                })
+            }));
+        }
+    }
+    days.sort_by_key(|day| (day.year, day.day));
+
+    days
+}
+
+// "thread 'main' panicked at src/main.rs:3:5:\nboom\nnote: ..." -> "boom"
+fn panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr.lines().skip_while(|l| !l.contains(" panicked at "));
+    let at = lines.next()?;
+    let message: Vec<&str> = lines.take_while(|l| !l.starts_with("note: ")).collect();
+
+    if message.is_empty() {
+        // before Rust 1.73 the message came first: "panicked at 'boom', src/..."
+        Some(at.split_once(" panicked at ")?.1.to_string())
+    } else {
+        Some(message.join("\n"))
+    }
+}
+
+/// Runs every day of every year in the repository holding the day crate in
+/// `dir`, and prints their answers next to their registries. Returns
+/// whether every day answered and matched its registry; days without one
+/// are only timed. With `--save`, the answers are written to the
+/// registries instead. A day that panics or runs longer than
+/// [`batch::timeout`] fails the run, but the others still run.
+pub fn run(dir: &Path) -> bool {
+    let save = std::env::args().any(|arg| arg == "--save");
+    let timeout = batch::timeout();
+
+    let mut ok = true;
+    for day in days(&root(dir)) {
+        let outcome = day.run(&[], timeout);
+        println!("{} {} ({:.2?})", day.year, day.name(), outcome.time);
+
+        let answers = match outcome.status {
+            Status::Answered(answers) => answers,
+            Status::Panicked(message) => {
+                ok = false;
+                println!("  panicked, {message}");
+                continue;
+            }
+            Status::TimedOut(timeout) => {
+                ok = false;
+                println!("  timed out after {timeout:.2?}");
+                continue;
+            }
+            Status::Failed(stderr) => {
+                ok = false;
+                println!("  failed");
+                for line in stderr.lines() {
+                    println!("    {line}");
+                }
+                continue;
+            }
+        };
+
+        let expected = day.expected().unwrap_or_default();
+        for (label, answer) in &answers {
+            let verdict = match expected.iter().find(|(l, _)| l == label) {
+                None => String::new(),
+                _ if save => String::new(),
+                Some((_, expected)) if expected == answer => "  ok".to_string(),
+                Some((_, expected)) => {
+                    ok = false;
+                    format!("  MISMATCH, expected {expected}")
+                }
+            };
+            println!("  {label}: {answer}{verdict}");
+        }
+
+        if save {
+            let registry: String = answers
+                .iter()
+                .map(|(label, answer)| format!("{label}: {answer}\n"))
+                .collect();
+            fs::write(day.registry(), registry)
+                .unwrap_or_else(|e| panic!("could not write {}: {e}", day.registry().display()));
+        }
+    }
+
+    ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_works() {
+        let repo = std::env::temp_dir().join(format!("shared-calendar-{}", std::process::id()));
+        for dir in ["day-02", "day-01", "2022/day-25", "shared", "2022/notes"] {
+            fs::create_dir_all(repo.join(dir)).unwrap();
+        }
+
+        let found: Vec<(u32, u32)> = days(&repo).iter().map(|d| (d.year, d.day)).collect();
+        assert_eq!(found, vec![(2022, 25), (YEAR, 1), (YEAR, 2)]);
+
+        assert_eq!(root(&repo.join("day-01")), repo);
+        assert_eq!(root(&repo.join("2022/day-25")), repo);
+
+        fs::remove_dir_all(&repo).unwrap();
+    }
+
+    #[test]
+    fn panic_message_works() {
+        let stderr = "thread 'main' panicked at src/main.rs:3:5:\nnot yet implemented\n\
+                      note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+        assert_eq!(
+            panic_message(stderr),
+            Some("not yet implemented".to_string())
+        );
+
+        let old = "thread 'main' panicked at 'boom', src/main.rs:3:5\n";
+        assert_eq!(
+            panic_message(old),
+            Some("'boom', src/main.rs:3:5".to_string())
+        );
+
+        assert_eq!(panic_message("error: could not compile\n"), None);
+    }
+}
@@ -0,0 +1,232 @@
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+use crate::{
+    batch,
+    calendar::{self, Day, Outcome, Status},
+};
+
+/// Terminal width used when `$COLUMNS` isn't set.
+const WIDTH: usize = 80;
+
+/// Separates the table from the output of the day that was run.
+const GUTTER: &str = " | ";
+
+/// Where a day stands, judged from a run on its input and its registry.
+#[derive(Debug, PartialEq)]
+enum State {
+    /// Every answer matches the registry.
+    Solved,
+    /// Some answer differs from the registry.
+    Failing,
+    /// It panicked, like on a `todo!()`.
+    Panicked,
+    /// It didn't build or exited with an error.
+    Failed,
+    /// It was killed for running too long.
+    TimedOut,
+    /// It answered, but the registry has no answers to check against.
+    Unverified,
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = match self {
+            State::Solved => "solved",
+            State::Failing => "failing",
+            State::Panicked => "panicked",
+            State::Failed => "failed",
+            State::TimedOut => "timed out",
+            State::Unverified => "unverified",
+        };
+        // pad, so the table can align it
+        f.pad(state)
+    }
+}
+
+fn state(status: &Status, expected: &[(String, String)]) -> State {
+    let answers = match status {
+        Status::Answered(answers) => answers,
+        Status::Panicked(_) => return State::Panicked,
+        Status::Failed(_) => return State::Failed,
+        Status::TimedOut(_) => return State::TimedOut,
+    };
+
+    let checked: Vec<bool> = answers
+        .iter()
+        .filter_map(|(label, answer)| {
+            let (_, expected) = expected.iter().find(|(l, _)| l == label)?;
+            Some(expected == answer)
+        })
+        .collect();
+    if checked.contains(&false) {
+        State::Failing
+    } else if checked.is_empty() {
+        State::Unverified
+    } else {
+        State::Solved
+    }
+}
+
+// the answer of the part labelled `label`, or "-"
+fn answer<'a>(status: &'a Status, label: &str) -> &'a str {
+    match status {
+        Status::Answered(answers) => answers
+            .iter()
+            .find(|(l, _)| l == label)
+            .map_or("-", |(_, a)| a.as_str()),
+        _ => "-",
+    }
+}
+
+fn table(days: &[(Day, Outcome)]) -> Vec<String> {
+    let mut lines = vec![format!(
+        "{:>2}  {:<4}  {:<6}  {:<10}  {:<15}  {:<15}  time",
+        "#", "year", "day", "status", "task 1", "task 2"
+    )];
+    for (i, (day, outcome)) in days.iter().enumerate() {
+        let state = state(&outcome.status, &day.expected().unwrap_or_default());
+        lines.push(format!(
+            "{:>2}  {:<4}  {:<6}  {state:<10}  {:<15}  {:<15}  {:.2?}",
+            i + 1,
+            day.year,
+            day.name(),
+            answer(&outcome.status, "task 1"),
+            answer(&outcome.status, "task 2"),
+            outcome.time,
+        ));
+    }
+
+    lines
+}
+
+// what a run printed, or how it went wrong
+fn pane(outcome: &Outcome) -> String {
+    match &outcome.status {
+        Status::Answered(_) => outcome.output.clone(),
+        Status::Panicked(message) => format!("{}panicked, {message}", outcome.output),
+        Status::Failed(stderr) => format!("{}failed\n{stderr}", outcome.output),
+        Status::TimedOut(timeout) => format!("{}timed out after {timeout:.2?}", outcome.output),
+    }
+}
+
+/// Lays `right` out next to `left`, cutting its lines off so no line is
+/// wider than `width`. The lines of `left` are padded to the widest one.
+fn side_by_side(left: &[String], right: &str, width: usize) -> Vec<String> {
+    let left_width = left.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let right_width = width.saturating_sub(left_width + GUTTER.len());
+    let right: Vec<&str> = right.lines().collect();
+
+    (0..left.len().max(right.len()))
+        .map(|i| {
+            let l = left.get(i).map_or("", String::as_str);
+            let r: String = right
+                .get(i)
+                .map_or("", |r| r)
+                .chars()
+                .take(right_width)
+                .collect();
+            format!("{l:<left_width$}{GUTTER}{r}")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+fn width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(WIDTH)
+}
+
+/// Runs every day in the repository holding the day crate in `dir`, each
+/// for at most [`batch::timeout`], and shows a table of how each went. Then reads day numbers from the table
+/// off stdin, runs that day with `--explain`, and shows what it printed
+/// next to the table, until stdin ends or `q` is entered.
+pub fn run(dir: &Path) {
+    println!("running every day...");
+    let timeout = batch::timeout();
+    let days: Vec<(Day, Outcome)> = calendar::days(&calendar::root(dir))
+        .into_iter()
+        .map(|day| {
+            let outcome = day.run(&[], timeout);
+            (day, outcome)
+        })
+        .collect();
+    let table = table(&days);
+
+    for line in &table {
+        println!("{line}");
+    }
+
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("run a day with --explain (#, q to quit): ");
+        io::stdout().flush().unwrap();
+
+        let Some(Ok(line)) = lines.next() else { break };
+        let line = line.trim();
+        if line == "q" {
+            break;
+        }
+
+        let Some((day, _)) = line
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| days.get(i.checked_sub(1)?))
+        else {
+            println!("no day #{line} in the table");
+            continue;
+        };
+
+        let explained = day.run(&["--explain"], timeout);
+        for line in side_by_side(&table, &pane(&explained), width()) {
+            println!("{line}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(answers: &[(&str, &str)]) -> Vec<(String, String)> {
+        answers
+            .iter()
+            .map(|(label, answer)| (label.to_string(), answer.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn state_works() {
+        let expected = answers(&[("task 1", "142"), ("task 2", "281")]);
+        let solved = Status::Answered(answers(&[("task 1", "142"), ("task 2", "281")]));
+        let failing = Status::Answered(answers(&[("task 1", "142"), ("task 2", "280")]));
+
+        assert_eq!(state(&solved, &expected), State::Solved);
+        assert_eq!(state(&failing, &expected), State::Failing);
+        assert_eq!(state(&solved, &[]), State::Unverified);
+        assert_eq!(
+            state(&Status::Panicked("not yet implemented".into()), &expected),
+            State::Panicked
+        );
+    }
+
+    #[test]
+    fn side_by_side_works() {
+        let left = ["# day".to_string(), "1 day-01".to_string()];
+
+        assert_eq!(
+            side_by_side(&left, "task 1: 142\ntask 2: 281\nsteps", 17),
+            vec!["# day    | task 1", "1 day-01 | task 2", "         | steps"]
+        );
+        assert_eq!(
+            side_by_side(&left, "", 17),
+            vec!["# day    |", "1 day-01 |"]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::{collections::HashMap, fmt::Display, fs, path::Path};
+
+use crate::input::normalize;
+
+/// Name of the manifest inside a day's `examples/` directory.
+///
+/// The first line names the columns: `file` followed by one column per
+/// solver (e.g. `part_1 part_2`). Every other line holds a fixture file name
+/// and its expected answers, with `-` where a fixture has no answer for that
+/// solver. Blank lines and lines starting with `#` are ignored.
+pub const MANIFEST: &str = "manifest.txt";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub file: String,
+    pub answers: HashMap<String, String>,
+}
+
+fn parse_manifest(manifest: &str) -> Vec<Example> {
+    let mut lines = manifest
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'));
+
+    let header: Vec<&str> = lines
+        .next()
+        .expect("manifest should have a header")
+        .split_whitespace()
+        .skip(1)
+        .collect();
+
+    lines
+        .map(|l| {
+            let mut fields = l.split_whitespace();
+            let file = fields.next().unwrap().to_string();
+            let answers = header
+                .iter()
+                .zip(fields)
+                .filter(|(_, answer)| *answer != "-")
+                .map(|(column, answer)| (column.to_string(), answer.to_string()))
+                .collect();
+
+            Example { file, answers }
+        })
+        .collect()
+}
+
+pub fn load(dir: &Path) -> Vec<Example> {
+    let manifest = fs::read_to_string(dir.join(MANIFEST))
+        .unwrap_or_else(|e| panic!("could not read {}: {e}", dir.join(MANIFEST).display()));
+
+    parse_manifest(&manifest)
+}
+
+/// Runs `solver` on every fixture in `dir` that has an answer in `column`,
+/// once as saved and once with CRLF line endings and a byte order mark, and
+/// panics with a list of all mismatches. Fixtures are normalized first, like
+/// the real input in `main`.
+pub fn check<T: Display>(dir: &Path, column: &str, solver: impl Fn(&str) -> T) {
+    let examples = load(dir);
+    let mut checked = 0;
+    let mut failures = vec![];
+
+    for example in examples.iter() {
+        let Some(expected) = example.answers.get(column) else {
+            continue;
+        };
+
+        let input = fs::read_to_string(dir.join(&example.file))
+            .unwrap_or_else(|e| panic!("could not read {}: {e}", example.file));
+        // the same fixture as saved by a Windows checkout
+        let crlf = format!("\u{feff}{}", input.replace('\n', "\r\n"));
+
+        for (variant, input) in [("", input), (" (crlf)", crlf)] {
+            let actual = solver(&normalize(&input)).to_string();
+            if actual != *expected {
+                failures.push(format!(
+                    "{}{variant}: expected {expected}, got {actual}",
+                    example.file
+                ));
+            }
+        }
+        checked += 1;
+    }
+
+    assert!(checked > 0, "no examples have an answer for {column}");
+    assert!(
+        failures.is_empty(),
+        "{column} failed:\n{}",
+        failures.join("\n")
+    );
+}
+
+/// Generates one test per solver that runs it against every fixture in the
+/// calling crate's `examples/` directory. The test is named after the
+/// column, or after `as` when several solvers share a column.
+///
+/// ```ignore
+/// shared::example_tests! {
+///     part_1 => task_1,
+///     part_2 => |input| task_2(input, 10),
+///     part_2 as part_2_closed_form => task_2_closed_form,
+/// }
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($($column:ident $(as $test:ident)? => $solver:expr),+ $(,)?) => {
+        $(
+            $crate::example_tests!(@test [$($test)? $column] $column $solver);
+        )+
+    };
+    (@test [$test:ident $($rest:ident)?] $column:ident $solver:expr) => {
+        #[test]
+        fn $test() {
+            $crate::examples::check(
+                &::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("examples"),
+                stringify!($column),
+                $solver,
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_manifest_works() {
+        let manifest = "# comment
+file        part_1  part_2
+
+example.txt 142     -
+twone.txt   -       21";
+
+        let examples = parse_manifest(manifest);
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].file, "example.txt");
+        assert_eq!(examples[0].answers.get("part_1").unwrap(), "142");
+        assert_eq!(examples[0].answers.get("part_2"), None);
+        assert_eq!(examples[1].answers.get("part_2").unwrap(), "21");
+    }
+
+    #[test]
+    fn parse_manifest_allows_missing_trailing_columns() {
+        let manifest = "file part_1 part_2
+example.txt 1";
+
+        let examples = parse_manifest(manifest);
+
+        assert_eq!(examples[0].answers.len(), 1);
+    }
+}
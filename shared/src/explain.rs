@@ -0,0 +1,32 @@
+use std::fmt::Display;
+
+/// An answer together with the human readable steps that produced it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explained<T> {
+    pub answer: T,
+    pub steps: Vec<String>,
+}
+
+impl<T: Display> Explained<T> {
+    pub fn new(answer: T, steps: Vec<String>) -> Self {
+        Explained { answer, steps }
+    }
+
+    /// Runs a solver that takes an `Option<&mut Vec<String>>` for its steps
+    /// with somewhere to write them. The same solver gets `None` when only
+    /// the answer is needed, so it doesn't format steps nobody reads.
+    pub fn record(solve: impl FnOnce(&mut Vec<String>) -> T) -> Self {
+        let mut steps = vec![];
+        let answer = solve(&mut steps);
+        Explained { answer, steps }
+    }
+
+    /// Prints the steps followed by `label: answer`, the same line `main`
+    /// prints without `--explain`.
+    pub fn print(&self, label: &str) {
+        for step in self.steps.iter() {
+            println!("  {step}");
+        }
+        println!("{label}: {}", self.answer);
+    }
+}
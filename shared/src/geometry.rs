@@ -0,0 +1,376 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A point on a grid. `x` grows to the east and `y` to the south, so row `y`
+/// of the puzzle input is `y` steps south of the first line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pos {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// The difference between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vec2 {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Pos {
+    pub fn new(x: i32, y: i32) -> Self {
+        Pos { x, y }
+    }
+
+    pub fn step(self, dir: Dir) -> Pos {
+        self + dir.offset()
+    }
+
+    pub fn manhattan(self, other: Pos) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(self, other: Pos) -> u32 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The four points sharing an edge with this one, clockwise from north.
+    pub fn neighbors(self) -> [Pos; 4] {
+        Dir::ALL.map(|dir| self.step(dir))
+    }
+
+    /// The eight points sharing an edge or a corner with this one,
+    /// clockwise from north.
+    pub fn neighbors8(self) -> [Pos; 8] {
+        Dir8::ALL.map(|dir| self + dir.offset())
+    }
+}
+
+impl Display for Pos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Vec2 {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl Add<Vec2> for Pos {
+    type Output = Pos;
+
+    fn add(self, rhs: Vec2) -> Pos {
+        Pos::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign<Vec2> for Pos {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Pos {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Pos) -> Vec2 {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Mul<i32> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: i32) -> Vec2 {
+        Vec2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+/// One of the four compass directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Dir {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// One of the eight compass directions: the four of [`Dir`] and the
+/// diagonals between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+/// A mirror standing diagonally in a grid cell, named after the character
+/// that draws it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Mirror {
+    Slash,
+    Backslash,
+}
+
+impl Dir {
+    /// Clockwise from north.
+    pub const ALL: [Dir; 4] = [Dir::North, Dir::East, Dir::South, Dir::West];
+
+    pub fn offset(self) -> Vec2 {
+        match self {
+            Dir::North => Vec2::new(0, -1),
+            Dir::East => Vec2::new(1, 0),
+            Dir::South => Vec2::new(0, 1),
+            Dir::West => Vec2::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Dir {
+        match self {
+            Dir::North => Dir::East,
+            Dir::East => Dir::South,
+            Dir::South => Dir::West,
+            Dir::West => Dir::North,
+        }
+    }
+
+    pub fn turn_left(self) -> Dir {
+        self.turn_right().opposite()
+    }
+
+    pub fn opposite(self) -> Dir {
+        self.turn_right().turn_right()
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Dir::East | Dir::West)
+    }
+
+    /// The direction something moving in `self` leaves `mirror` in.
+    pub fn reflect(self, mirror: Mirror) -> Dir {
+        match (mirror, self.is_horizontal()) {
+            (Mirror::Slash, true) | (Mirror::Backslash, false) => self.turn_left(),
+            (Mirror::Slash, false) | (Mirror::Backslash, true) => self.turn_right(),
+        }
+    }
+}
+
+impl Dir8 {
+    /// Clockwise from north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::North,
+        Dir8::NorthEast,
+        Dir8::East,
+        Dir8::SouthEast,
+        Dir8::South,
+        Dir8::SouthWest,
+        Dir8::West,
+        Dir8::NorthWest,
+    ];
+
+    pub fn offset(self) -> Vec2 {
+        match self {
+            Dir8::North => Vec2::new(0, -1),
+            Dir8::NorthEast => Vec2::new(1, -1),
+            Dir8::East => Vec2::new(1, 0),
+            Dir8::SouthEast => Vec2::new(1, 1),
+            Dir8::South => Vec2::new(0, 1),
+            Dir8::SouthWest => Vec2::new(-1, 1),
+            Dir8::West => Vec2::new(-1, 0),
+            Dir8::NorthWest => Vec2::new(-1, -1),
+        }
+    }
+
+    /// Turns an eighth of a circle clockwise.
+    pub fn turn_right(self) -> Dir8 {
+        let i = Dir8::ALL.iter().position(|&d| d == self).unwrap();
+        Dir8::ALL[(i + 1) % 8]
+    }
+
+    /// Turns an eighth of a circle counterclockwise.
+    pub fn turn_left(self) -> Dir8 {
+        self.opposite().turn_right().turn_right().turn_right()
+    }
+
+    pub fn opposite(self) -> Dir8 {
+        self.turn_right().turn_right().turn_right().turn_right()
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        Dir::try_from(self).is_err()
+    }
+}
+
+impl From<Dir> for Dir8 {
+    fn from(dir: Dir) -> Self {
+        match dir {
+            Dir::North => Dir8::North,
+            Dir::East => Dir8::East,
+            Dir::South => Dir8::South,
+            Dir::West => Dir8::West,
+        }
+    }
+}
+
+/// Fails for the diagonals, which have no four-way counterpart.
+impl TryFrom<Dir8> for Dir {
+    type Error = Dir8;
+
+    fn try_from(dir: Dir8) -> Result<Self, Dir8> {
+        match dir {
+            Dir8::North => Ok(Dir::North),
+            Dir8::East => Ok(Dir::East),
+            Dir8::South => Ok(Dir::South),
+            Dir8::West => Ok(Dir::West),
+            diagonal => Err(diagonal),
+        }
+    }
+}
+
+/// The smallest rectangle holding a set of points, both corners inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bounds {
+    pub min: Pos,
+    pub max: Pos,
+}
+
+impl Bounds {
+    /// Bounds of a grid with `width` columns and `height` rows, starting at
+    /// the origin.
+    pub fn of_grid(width: usize, height: usize) -> Self {
+        Bounds {
+            min: Pos::new(0, 0),
+            max: Pos::new(width as i32 - 1, height as i32 - 1),
+        }
+    }
+
+    /// `None` when there are no points.
+    pub fn of(points: impl IntoIterator<Item = Pos>) -> Option<Self> {
+        points.into_iter().fold(None, |bounds, p| {
+            Some(match bounds {
+                None => Bounds { min: p, max: p },
+                Some(Bounds { min, max }) => Bounds {
+                    min: Pos::new(min.x.min(p.x), min.y.min(p.y)),
+                    max: Pos::new(max.x.max(p.x), max.y.max(p.y)),
+                },
+            })
+        })
+    }
+
+    pub fn contains(&self, p: Pos) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.min.x.abs_diff(self.max.x) + 1
+    }
+
+    pub fn height(&self) -> u32 {
+        self.min.y.abs_diff(self.max.y) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_works() {
+        let a = Pos::new(1, 6);
+        let b = Pos::new(5, 11);
+
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(a.chebyshev(b), 5);
+        assert_eq!(b - a, Vec2::new(4, 5));
+        assert_eq!(a + (b - a) * 2, Pos::new(9, 16));
+    }
+
+    #[test]
+    fn dir_works() {
+        assert_eq!(Pos::new(0, 0).step(Dir::North), Pos::new(0, -1));
+        assert_eq!(Dir::North.turn_left(), Dir::West);
+        assert_eq!(Dir::West.turn_right(), Dir::North);
+        assert_eq!(Dir::East.opposite(), Dir::West);
+
+        // a beam heading east turns north at `/` and south at `\`
+        assert_eq!(Dir::East.reflect(Mirror::Slash), Dir::North);
+        assert_eq!(Dir::North.reflect(Mirror::Slash), Dir::East);
+        assert_eq!(Dir::East.reflect(Mirror::Backslash), Dir::South);
+        assert_eq!(Dir::South.reflect(Mirror::Backslash), Dir::East);
+        assert_eq!(Dir::West.reflect(Mirror::Backslash), Dir::North);
+    }
+
+    #[test]
+    fn dir8_works() {
+        assert_eq!(Dir8::North.turn_right(), Dir8::NorthEast);
+        assert_eq!(Dir8::North.turn_left(), Dir8::NorthWest);
+        assert_eq!(Dir8::SouthWest.opposite(), Dir8::NorthEast);
+        assert_eq!(Dir8::SouthWest.offset(), -Dir8::NorthEast.offset());
+        assert!(Dir8::NorthWest.is_diagonal());
+        assert_eq!(Dir::try_from(Dir8::from(Dir::West)), Ok(Dir::West));
+        assert_eq!(Dir::try_from(Dir8::SouthEast), Err(Dir8::SouthEast));
+    }
+
+    #[test]
+    fn neighbors_works() {
+        let p = Pos::new(2, 2);
+
+        assert_eq!(
+            p.neighbors().iter().filter(|n| n.manhattan(p) == 1).count(),
+            4
+        );
+        assert_eq!(
+            p.neighbors8()
+                .iter()
+                .filter(|n| n.chebyshev(p) == 1)
+                .count(),
+            8
+        );
+    }
+
+    #[test]
+    fn bounds_works() {
+        let bounds = Bounds::of([Pos::new(3, -1), Pos::new(-2, 4), Pos::new(0, 0)]).unwrap();
+
+        assert_eq!(bounds.min, Pos::new(-2, -1));
+        assert_eq!(bounds.max, Pos::new(3, 4));
+        assert_eq!((bounds.width(), bounds.height()), (6, 6));
+        assert!(bounds.contains(Pos::new(3, 4)));
+        assert!(!bounds.contains(Pos::new(4, 0)));
+        assert!(Bounds::of([]).is_none());
+        assert!(!Bounds::of_grid(10, 10).contains(Pos::new(10, 0)));
+    }
+}
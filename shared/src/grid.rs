@@ -0,0 +1,248 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A rectangular grid of cells, stored row by row. `x` is the column and
+/// `y` the row, counted from the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// One of the eight symmetries of a rectangle. Rotations are clockwise,
+/// `FlipHorizontal` mirrors left and right and `FlipVertical` top and
+/// bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Transform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Transpose,
+    AntiTranspose,
+    FlipHorizontal,
+    FlipVertical,
+}
+
+/// A grid seen through a [`Transform`], without copying any cells.
+#[derive(Debug, Clone, Copy)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    transform: Transform,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "grid should be {width}x{height}"
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Panics if the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        for (y, row) in rows.iter().enumerate() {
+            assert_eq!(row.len(), width, "row {y} should be as long as row 0");
+        }
+        Grid::new(width, height, rows.into_iter().flatten().collect())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn view(&self, transform: Transform) -> View<'_, T> {
+        View {
+            grid: self,
+            transform,
+        }
+    }
+
+    /// A copy of the grid with `transform` applied.
+    pub fn transformed(&self, transform: Transform) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.view(transform).to_grid()
+    }
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::Transpose,
+        Transform::AntiTranspose,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+    ];
+
+    /// Whether width and height trade places.
+    pub fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90
+                | Transform::Rotate270
+                | Transform::Transpose
+                | Transform::AntiTranspose
+        )
+    }
+
+    // where (x, y) of the transformed grid is in a `width` x `height` grid
+    fn source(self, x: usize, y: usize, width: usize, height: usize) -> (usize, usize) {
+        match self {
+            Transform::Identity => (x, y),
+            Transform::Rotate90 => (y, height - 1 - x),
+            Transform::Rotate180 => (width - 1 - x, height - 1 - y),
+            Transform::Rotate270 => (width - 1 - y, x),
+            Transform::Transpose => (y, x),
+            Transform::AntiTranspose => (width - 1 - y, height - 1 - x),
+            Transform::FlipHorizontal => (width - 1 - x, y),
+            Transform::FlipVertical => (x, height - 1 - y),
+        }
+    }
+}
+
+impl<'a, T> View<'a, T> {
+    pub fn width(&self) -> usize {
+        if self.transform.swaps_axes() {
+            self.grid.height
+        } else {
+            self.grid.width
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        if self.transform.swaps_axes() {
+            self.grid.width
+        } else {
+            self.grid.height
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a T> {
+        if x >= self.width() || y >= self.height() {
+            return None;
+        }
+
+        let (x, y) = self
+            .transform
+            .source(x, y, self.grid.width, self.grid.height);
+        self.grid.get(x, y)
+    }
+
+    /// Panics if `y` is out of the view.
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &'a T> + '_ {
+        assert!(y < self.height(), "row {y} is out of the view");
+        (0..self.width()).map(move |x| self.get(x, y).unwrap())
+    }
+
+    /// Panics if `x` is out of the view.
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &'a T> + '_ {
+        assert!(x < self.width(), "column {x} is out of the view");
+        (0..self.height()).map(move |y| self.get(x, y).unwrap())
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.height())
+            .flat_map(|y| self.row(y).cloned().collect::<Vec<_>>())
+            .collect();
+        Grid::new(self.width(), self.height(), cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(grid: &Grid<char>) -> Vec<String> {
+        grid.rows().map(|row| row.iter().collect()).collect()
+    }
+
+    #[test]
+    fn transforms_work() {
+        // abc
+        // def
+        let grid = Grid::from_rows(vec!["abc".chars().collect(), "def".chars().collect()]);
+
+        let expected = [
+            (Transform::Identity, vec!["abc", "def"]),
+            (Transform::Rotate90, vec!["da", "eb", "fc"]),
+            (Transform::Rotate180, vec!["fed", "cba"]),
+            (Transform::Rotate270, vec!["cf", "be", "ad"]),
+            (Transform::Transpose, vec!["ad", "be", "cf"]),
+            (Transform::AntiTranspose, vec!["fc", "eb", "da"]),
+            (Transform::FlipHorizontal, vec!["cba", "fed"]),
+            (Transform::FlipVertical, vec!["def", "abc"]),
+        ];
+        for (transform, rows_after) in expected {
+            assert_eq!(
+                rows(&grid.transformed(transform)),
+                rows_after,
+                "{transform:?}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 should be as long as row 0")]
+    fn ragged_rows_panic() {
+        // three cells, like a 1x3 grid, but not in rows of one
+        Grid::from_rows(vec![vec![1], vec![2, 3], vec![]]);
+    }
+
+    #[test]
+    fn view_works() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let view = grid.view(Transform::Rotate90);
+
+        assert_eq!((view.width(), view.height()), (2, 3));
+        assert_eq!(view.get(0, 0), Some(&4));
+        assert_eq!(view.get(2, 0), None);
+        assert_eq!(view.row(1).copied().collect::<Vec<_>>(), vec![5, 2]);
+        assert_eq!(view.col(1).copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn rotating_four_times_is_identity() {
+        let mut grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let original = grid.clone();
+
+        for _ in 0..4 {
+            grid = grid.transformed(Transform::Rotate90);
+        }
+        assert_eq!(grid, original);
+    }
+}
@@ -0,0 +1,25 @@
+/// Brings puzzle input into the shape the solutions expect: no byte order
+/// mark, `\n` line endings, no trailing whitespace on any line and no
+/// newline after the last line.
+pub fn normalize(input: &str) -> String {
+    input
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_works() {
+        assert_eq!(normalize("\u{feff}ab \r\ncd\r\n\r\n"), "ab\ncd");
+        assert_eq!(normalize("ab\n  \ncd\n"), "ab\n\ncd");
+        assert_eq!(normalize("ab\ncd"), "ab\ncd");
+    }
+}
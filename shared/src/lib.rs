@@ -0,0 +1,17 @@
+pub mod assumptions;
+pub mod batch;
+pub mod calendar;
+pub mod dashboard;
+pub mod examples;
+pub mod explain;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod memo;
+pub mod parse;
+pub mod perf;
+pub mod ranges;
+pub mod report;
+pub mod stats;
+pub mod variants;
+pub mod watch;
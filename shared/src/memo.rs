@@ -0,0 +1,105 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// A cache for the results of a recursive function. Create one per
+/// top-level call and pass it down, so nothing is kept between calls.
+///
+/// Keys can borrow from the input (`&str`, `&[T]`) or be plain indices into
+/// it.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    capacity: Option<usize>,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            capacity: None,
+        }
+    }
+
+    /// A memo that stops storing new results once it holds `capacity` of
+    /// them. Results past that are computed every time they are needed.
+    pub fn bounded(capacity: usize) -> Self {
+        Memo {
+            cache: HashMap::new(),
+            capacity: Some(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Returns the result stored for `key`, or computes it with `compute`,
+    /// which gets the memo back to make its recursive calls with.
+    pub fn get(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+
+        let value = compute(self);
+        if self
+            .capacity
+            .is_none_or(|capacity| self.cache.len() < capacity)
+        {
+            self.cache.insert(key, value.clone());
+        }
+
+        value
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.get(n, |memo| match n {
+            0 | 1 => n,
+            _ => fib(memo, n - 1) + fib(memo, n - 2),
+        })
+    }
+
+    // ways to build `target` out of `parts`, keyed by the borrowed rest
+    fn arrangements<'a>(memo: &mut Memo<&'a str, u64>, target: &'a str, parts: &[&str]) -> u64 {
+        memo.get(target, |memo| {
+            if target.is_empty() {
+                return 1;
+            }
+            parts
+                .iter()
+                .filter_map(|part| target.strip_prefix(part))
+                .map(|rest| arrangements(memo, rest, parts))
+                .sum()
+        })
+    }
+
+    #[test]
+    fn memo_works() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+
+        assert_eq!(arrangements(&mut Memo::new(), "abab", &["a", "b", "ab"]), 4);
+    }
+
+    #[test]
+    fn bounded_works() {
+        let mut memo = Memo::bounded(10);
+
+        assert_eq!(fib(&mut memo, 30), 832040);
+        assert_eq!(memo.len(), 10);
+    }
+}
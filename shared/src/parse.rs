@@ -0,0 +1,102 @@
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, anychar, char, digit1, newline, space0, space1},
+    combinator::{map_opt, map_res, opt, recognize},
+    error::Error,
+    multi::{many1, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair},
+    IResult, Parser,
+};
+
+/// Parses an integer with an optional minus sign into any `T: FromStr`,
+/// e.g. `-12` into `-12i32`.
+pub fn integer<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(input)
+}
+
+/// Parses space separated integers after optional leading spaces, e.g.
+/// `"  41 48  6 17"` into `vec![41, 48, 6, 17]`.
+pub fn numbers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    preceded(space0, separated_list1(space1, integer))(input)
+}
+
+/// Parses blank line separated blocks with `block`.
+pub fn blocks<'a, O, F>(block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(tag("\n\n"), block)
+}
+
+/// Parses a character grid into rows of `T`, using `cell` to map every
+/// character. The grid ends at the first character `cell` rejects.
+pub fn grid<'a, T>(
+    cell: impl Fn(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<T>>> {
+    separated_list1(newline, many1(map_opt(anychar, cell)))
+}
+
+/// Parses a `name = (left, right)` line of alphanumeric names, e.g.
+/// `"AAA = (BBB, CCC)"` into `("AAA", ("BBB", "CCC"))`.
+pub fn record(input: &str) -> IResult<&str, (&str, (&str, &str))> {
+    separated_pair(
+        alphanumeric1,
+        tag(" = "),
+        delimited(
+            tag("("),
+            separated_pair(alphanumeric1, tag(", "), alphanumeric1),
+            tag(")"),
+        ),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_works() {
+        assert_eq!(
+            numbers::<u32>("  1 21 53 | 69"),
+            Ok((" | 69", vec![1, 21, 53]))
+        );
+        assert_eq!(numbers::<i64>("10 -3  7\n1"), Ok(("\n1", vec![10, -3, 7])));
+        assert!(numbers::<u8>("-3").is_err());
+    }
+
+    #[test]
+    fn blocks_works() {
+        let input = "1 2\n3\n\n4 5";
+
+        let result = blocks(separated_list1(newline, numbers::<u32>))(input);
+        assert_eq!(
+            result,
+            Ok(("", vec![vec![vec![1, 2], vec![3]], vec![vec![4, 5]]]))
+        );
+    }
+
+    #[test]
+    fn grid_works() {
+        let input = "#.\n.#\n\n##";
+
+        let result = grid(|c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })(input);
+        assert_eq!(
+            result,
+            Ok(("\n\n##", vec![vec![true, false], vec![false, true]]))
+        );
+    }
+
+    #[test]
+    fn record_works() {
+        assert_eq!(
+            record("11A = (11B, XXX)\n"),
+            Ok(("\n", ("11A", ("11B", "XXX"))))
+        );
+    }
+}
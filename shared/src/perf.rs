@@ -0,0 +1,123 @@
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::batch::Part;
+
+/// File in a day's crate directory holding its timing baseline, one
+/// `task N: nanoseconds` line per part. Timings only compare on the same
+/// machine, so the file is not committed.
+pub const BASELINE: &str = "perf-baseline.txt";
+
+/// Runs per part; the median is compared.
+const REPETITIONS: usize = 10;
+
+/// How much slower than its baseline a part may get before it counts as a
+/// regression, to ride out noise between runs.
+const NOISE_THRESHOLD: f64 = 0.2;
+
+/// Parts this much slower or less never count as regressed, so the
+/// threshold doesn't trip on parts that only take microseconds.
+const MIN_SLOWDOWN: Duration = Duration::from_micros(100);
+
+fn median_time(input: &str, solve: &dyn Fn(&str) -> String) -> Duration {
+    let mut times: Vec<Duration> = (0..REPETITIONS)
+        .map(|_| {
+            let start = Instant::now();
+            solve(input);
+            start.elapsed()
+        })
+        .collect();
+    times.sort();
+
+    times[times.len() / 2]
+}
+
+// "task 1: 1500" -> ("task 1", 1.5µs)
+fn parse_baseline(baseline: &str) -> Vec<(String, Duration)> {
+    baseline
+        .lines()
+        .filter_map(|l| l.split_once(": "))
+        .map(|(label, nanos)| {
+            let nanos = nanos
+                .trim()
+                .parse()
+                .expect("baseline should hold nanoseconds");
+            (label.to_string(), Duration::from_nanos(nanos))
+        })
+        .collect()
+}
+
+/// Times every part on `input` and compares it with the baseline in
+/// `dir`, printing how each part changed. Returns whether no part got
+/// slower than the noise threshold allows. Without a baseline, or with
+/// `--save`, the timings become the new baseline instead.
+pub fn run(dir: &Path, input: &str, parts: &[Part]) -> bool {
+    if cfg!(debug_assertions) {
+        eprintln!("warning: timing a debug build, run with --release");
+    }
+
+    let times: Vec<Duration> = parts
+        .iter()
+        .map(|part| median_time(input, &*part.solve))
+        .collect();
+
+    let path = dir.join(BASELINE);
+    if std::env::args().any(|arg| arg == "--save") || !path.exists() {
+        let baseline: String = parts
+            .iter()
+            .zip(times.iter())
+            .map(|(part, time)| format!("{}: {}\n", part.label, time.as_nanos()))
+            .collect();
+        fs::write(&path, baseline)
+            .unwrap_or_else(|e| panic!("could not write {}: {e}", path.display()));
+
+        for (part, time) in parts.iter().zip(times.iter()) {
+            println!("{}: {time:.2?}", part.label);
+        }
+        println!("saved as baseline in {}", path.display());
+        return true;
+    }
+
+    let baseline = parse_baseline(&fs::read_to_string(&path).unwrap());
+    let mut ok = true;
+    for (part, time) in parts.iter().zip(times.iter()) {
+        let label = part.label;
+        let Some((_, before)) = baseline.iter().find(|(l, _)| l == label) else {
+            println!("{label}: {time:.2?} (no baseline)");
+            continue;
+        };
+
+        let change = time.as_secs_f64() / before.as_secs_f64() - 1.0;
+        let verdict = if change > NOISE_THRESHOLD && time.saturating_sub(*before) > MIN_SLOWDOWN {
+            ok = false;
+            "  REGRESSED"
+        } else {
+            ""
+        };
+        println!(
+            "{label}: {time:.2?} (baseline {before:.2?}, {:+.0}%){verdict}",
+            change * 100.0
+        );
+    }
+
+    ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_baseline_works() {
+        assert_eq!(
+            parse_baseline("task 1: 1500\ntask 2: 2000000\n"),
+            vec![
+                ("task 1".to_string(), Duration::from_nanos(1500)),
+                ("task 2".to_string(), Duration::from_millis(2))
+            ]
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::ops::{Add, Range, Sub};
+
+/// A set of values stored as half-open ranges.
+///
+/// The ranges are kept normalized: sorted, non-empty, and neither overlapping
+/// nor touching, so two sets holding the same values compare equal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    pub fn new() -> Self {
+        RangeSet { ranges: vec![] }
+    }
+
+    fn normalize(mut ranges: Vec<Range<T>>) -> Vec<Range<T>> {
+        ranges.retain(|r| r.start < r.end);
+        ranges.sort_by_key(|r| r.start);
+
+        let mut normalized: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match normalized.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => normalized.push(r),
+            }
+        }
+
+        normalized
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges.iter().any(|r| r.contains(&value))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        self.ranges.push(range);
+        self.ranges = Self::normalize(std::mem::take(&mut self.ranges));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.ranges
+            .iter()
+            .chain(other.ranges.iter())
+            .cloned()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (a, b) = (&self.ranges, &other.ranges);
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = vec![];
+
+        while i < a.len() && j < b.len() {
+            ranges.push(a[i].start.max(b[j].start)..a[i].end.min(b[j].end));
+            if a[i].end < b[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        ranges.into_iter().collect()
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let others = &other.ranges;
+        let mut first = 0;
+        let mut ranges = vec![];
+
+        for r in self.ranges.iter() {
+            // ranges of `other` ending before `r` can't touch any later range
+            while first < others.len() && others[first].end <= r.start {
+                first += 1;
+            }
+
+            let mut start = r.start;
+            for o in others[first..].iter().take_while(|o| o.start < r.end) {
+                ranges.push(start..o.start);
+                start = start.max(o.end);
+            }
+            ranges.push(start..r.end);
+        }
+
+        ranges.into_iter().collect()
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> RangeSet<T> {
+    /// Moves the values in every `(source, dest)` piece so that
+    /// `source.start` lands on `dest`, keeping values no piece covers where
+    /// they are. Where pieces overlap the first one wins.
+    pub fn map_piecewise(&self, pieces: impl IntoIterator<Item = (Range<T>, T)>) -> Self {
+        let mut unmapped = self.clone();
+        let mut mapped = vec![];
+
+        for (source, dest) in pieces {
+            let piece = RangeSet::from(source.clone());
+            for r in unmapped.intersection(&piece).ranges {
+                mapped.push(dest + (r.start - source.start)..dest + (r.end - source.start));
+            }
+            unmapped = unmapped.difference(&piece);
+        }
+
+        mapped.into_iter().chain(unmapped.ranges).collect()
+    }
+}
+
+impl<T: Copy + Ord> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        [range].into_iter().collect()
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        RangeSet {
+            ranges: Self::normalize(iter.into_iter().collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn normalize_works() {
+        let set: RangeSet<u32> = [5..7, 0..2, 1..3, 3..4, 9..9].into_iter().collect();
+
+        assert_eq!(set.ranges(), &[0..4, 5..7]);
+        assert_eq!(set.min(), Some(0));
+        assert!(set.contains(6));
+        assert!(!set.contains(4));
+    }
+
+    #[test]
+    fn map_piecewise_works() {
+        // the first map of the day 5 example, applied to seeds 79..93
+        let set = RangeSet::from(79u64..93);
+
+        let mapped = set.map_piecewise([(98..100, 50), (50..98, 52)]);
+
+        assert_eq!(mapped, RangeSet::from(81..95));
+    }
+
+    fn values(set: &RangeSet<u16>) -> BTreeSet<u16> {
+        set.ranges().iter().flat_map(|r| r.clone()).collect()
+    }
+
+    fn range_set() -> impl Strategy<Value = RangeSet<u16>> {
+        prop::collection::vec((0u16..64, 0u16..16), 0..6)
+            .prop_map(|rs| rs.into_iter().map(|(s, len)| s..s + len).collect())
+    }
+
+    proptest! {
+        #[test]
+        fn stays_normalized(a in range_set(), b in range_set()) {
+            for set in [a.union(&b), a.intersection(&b), a.difference(&b)] {
+                prop_assert!(set.ranges().iter().all(|r| r.start < r.end));
+                prop_assert!(set.ranges().windows(2).all(|w| w[0].end < w[1].start));
+            }
+        }
+
+        #[test]
+        fn set_operations_match_btreeset(a in range_set(), b in range_set()) {
+            let (va, vb) = (values(&a), values(&b));
+
+            prop_assert_eq!(values(&a.union(&b)), &va | &vb);
+            prop_assert_eq!(values(&a.intersection(&b)), &va & &vb);
+            prop_assert_eq!(values(&a.difference(&b)), &va - &vb);
+        }
+
+        #[test]
+        fn map_piecewise_matches_values(
+            a in range_set(),
+            pieces in prop::collection::vec((0u16..64, 0u16..16, 0u16..64), 0..4),
+        ) {
+            let pieces = pieces
+                .into_iter()
+                .map(|(s, len, dest)| (s..s + len, dest))
+                .collect::<Vec<_>>();
+
+            let expected = values(&a)
+                .into_iter()
+                .map(|v| match pieces.iter().find(|(source, _)| source.contains(&v)) {
+                    Some((source, dest)) => dest + (v - source.start),
+                    None => v,
+                })
+                .collect::<BTreeSet<_>>();
+
+            prop_assert_eq!(values(&a.map_piecewise(pieces)), expected);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::{fs, path::Path};
+
+use crate::{
+    batch,
+    calendar::{self, Day, Outcome, Status},
+};
+
+/// The README lines the report goes between. Everything outside them is
+/// left alone.
+const START: &str = "<!-- calendar -->";
+const END: &str = "<!-- /calendar -->";
+
+const README: &str = "README.md";
+
+// how far a part got: checked against the registry, or only answered
+fn completion(status: &Status, expected: &[(String, String)], label: &str) -> &'static str {
+    let answer = match status {
+        Status::Answered(answers) => answers.iter().find(|(l, _)| l == label),
+        Status::Panicked(_) => return "panicked",
+        Status::Failed(_) => return "failed",
+        Status::TimedOut(_) => return "timed out",
+    };
+
+    match (answer, expected.iter().find(|(l, _)| l == label)) {
+        (None, _) => "-",
+        (Some(_), None) => "unverified",
+        (Some((_, answer)), Some((_, expected))) if answer == expected => "done",
+        (Some(_), Some(_)) => "wrong",
+    }
+}
+
+fn table(root: &Path, days: &[(Day, Outcome)]) -> String {
+    let mut table = "| Year | Day | Part 1 | Part 2 | Runtime |\n\
+                     |------|-----|--------|--------|---------|\n"
+        .to_string();
+    for (day, outcome) in days {
+        let expected = day.expected().unwrap_or_default();
+        let solution = day.dir.join("src/main.rs");
+        let solution = solution.strip_prefix(root).unwrap_or(&solution);
+        table += &format!(
+            "| {} | [{}]({}) | {} | {} | {:.2?} |\n",
+            day.year,
+            day.name(),
+            solution.display(),
+            completion(&outcome.status, &expected, "task 1"),
+            completion(&outcome.status, &expected, "task 2"),
+            outcome.time,
+        );
+    }
+
+    table
+}
+
+/// `readme` with what's between the report markers replaced by `section`,
+/// or with the markers and `section` added at the end if it has none.
+fn replace_section(readme: &str, section: &str) -> String {
+    let replaced = readme.split_once(START).and_then(|(before, rest)| {
+        let (_, after) = rest.split_once(END)?;
+        Some(format!("{before}{START}\n{section}{END}{after}"))
+    });
+
+    replaced.unwrap_or_else(|| format!("{}\n\n{START}\n{section}{END}\n", readme.trim_end()))
+}
+
+/// Runs every day in the repository holding the day crate in `dir`, and
+/// writes a table of which parts are done and how long each day takes into
+/// the repository's README, between the report markers.
+pub fn run(dir: &Path) {
+    let root = calendar::root(dir);
+    let timeout = batch::timeout();
+    let days: Vec<(Day, Outcome)> = calendar::days(&root)
+        .into_iter()
+        .map(|day| {
+            let outcome = day.run(&[], timeout);
+            (day, outcome)
+        })
+        .collect();
+
+    let path = root.join(README);
+    let readme = fs::read_to_string(&path).unwrap_or_default();
+    fs::write(&path, replace_section(&readme, &table(&root, &days)))
+        .unwrap_or_else(|e| panic!("could not write {}: {e}", path.display()));
+    println!("updated {}", path.display());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_section_works() {
+        let table = "| Day |\n";
+
+        assert_eq!(
+            replace_section("# Advent of Code", table),
+            "# Advent of Code\n\n<!-- calendar -->\n| Day |\n<!-- /calendar -->\n"
+        );
+        assert_eq!(
+            replace_section(
+                "# Advent\n\n<!-- calendar -->\n| Old |\n<!-- /calendar -->\n\nMore\n",
+                table
+            ),
+            "# Advent\n\n<!-- calendar -->\n| Day |\n<!-- /calendar -->\n\nMore\n"
+        );
+    }
+
+    #[test]
+    fn completion_works() {
+        let answered = Status::Answered(vec![("task 1".to_string(), "142".to_string())]);
+        let expected = [("task 1".to_string(), "142".to_string())];
+
+        assert_eq!(completion(&answered, &expected, "task 1"), "done");
+        assert_eq!(completion(&answered, &expected, "task 2"), "-");
+        assert_eq!(completion(&answered, &[], "task 1"), "unverified");
+        assert_eq!(
+            completion(&Status::Panicked("boom".to_string()), &expected, "task 1"),
+            "panicked"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+/// Named measurements of a puzzle input, printed one per line.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stats(pub Vec<(String, String)>);
+
+impl Stats {
+    pub fn new() -> Self {
+        Stats(vec![])
+    }
+
+    pub fn add(&mut self, name: impl Into<String>, value: impl Display) -> &mut Self {
+        self.0.push((name.into(), value.to_string()));
+        self
+    }
+
+    pub fn print(&self) {
+        let width = self.0.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        for (name, value) in self.0.iter() {
+            println!("{name:<width$}  {value}");
+        }
+    }
+}
+
+/// The range of `values`: `[3, 3]` -> `3`, `[5, 3, 4]` -> `3..=5`, `[]` ->
+/// `-`.
+pub fn span(values: &[usize]) -> String {
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return "-".to_string();
+    };
+
+    if min == max {
+        min.to_string()
+    } else {
+        format!("{min}..={max}")
+    }
+}
+
+/// Counts the blank line separated grids in `input`, their widths and
+/// heights, and how often every character occurs in them.
+pub fn grids(input: &str) -> Stats {
+    let grids: Vec<Vec<&str>> = input
+        .split("\n\n")
+        .map(|grid| grid.lines().collect::<Vec<_>>())
+        .filter(|grid| !grid.is_empty())
+        .collect();
+    let widths: Vec<usize> = grids
+        .iter()
+        .flatten()
+        .map(|row| row.chars().count())
+        .collect();
+    let heights: Vec<usize> = grids.iter().map(Vec::len).collect();
+
+    let mut symbols: BTreeMap<char, usize> = BTreeMap::new();
+    for c in grids.iter().flatten().flat_map(|row| row.chars()) {
+        *symbols.entry(c).or_default() += 1;
+    }
+
+    let mut stats = Stats::new();
+    stats
+        .add("grids", grids.len())
+        .add("width", span(&widths))
+        .add("height", span(&heights));
+    for (c, count) in symbols {
+        stats.add(format!("'{c}'"), count);
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grids_works() {
+        let stats = grids("#.#\n..#\n\n##\n.#\n##");
+
+        assert_eq!(
+            stats.0,
+            [
+                ("grids", "2"),
+                ("width", "2..=3"),
+                ("height", "2..=3"),
+                ("'#'", "8"),
+                ("'.'", "4"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+    }
+
+    #[test]
+    fn empty_input_works() {
+        assert_eq!(span(&[]), "-");
+        assert_eq!(
+            grids("").0,
+            [("grids", "0"), ("width", "-"), ("height", "-")]
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt::Display;
+
+/// One way of solving a part. The first variant of a part is the one `main`
+/// runs unless `--variant <name>` asks for another.
+pub struct Variant<'a, T> {
+    pub name: &'static str,
+    pub solve: Box<dyn Fn(&str) -> T + 'a>,
+}
+
+impl<'a, T> Variant<'a, T> {
+    pub fn new(name: &'static str, solve: impl Fn(&str) -> T + 'a) -> Self {
+        Variant {
+            name,
+            solve: Box::new(solve),
+        }
+    }
+}
+
+/// The name passed with `--variant`, if any.
+pub fn requested() -> Option<String> {
+    std::env::args().skip_while(|arg| arg != "--variant").nth(1)
+}
+
+/// Runs the requested variant, or the first one, and prints
+/// `label: answer`.
+pub fn run<T: Display>(label: &str, input: &str, variants: &[Variant<T>]) {
+    let variant = match requested() {
+        None => &variants[0],
+        Some(name) => variants.iter().find(|v| v.name == name).unwrap_or_else(|| {
+            let names: Vec<_> = variants.iter().map(|v| v.name).collect();
+            panic!("no variant {name}, expected one of {}", names.join(", "))
+        }),
+    };
+
+    println!("{label}: {}", (variant.solve)(input));
+}
+
+/// Runs every variant, prints their answers and returns whether they all
+/// agree.
+pub fn verify<T: Display + PartialEq>(label: &str, input: &str, variants: &[Variant<T>]) -> bool {
+    let answers: Vec<T> = variants.iter().map(|v| (v.solve)(input)).collect();
+    let agree = answers.iter().all(|a| *a == answers[0]);
+
+    println!("{label}: {}", if agree { "ok" } else { "DIFFERS" });
+    for (variant, answer) in variants.iter().zip(answers.iter()) {
+        println!("  {:<12}{answer}", variant.name);
+    }
+
+    agree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_works() {
+        let variants = [
+            Variant::new("len", |input: &str| input.len()),
+            Variant::new("count", |input: &str| input.chars().count()),
+        ];
+
+        assert!(verify("ascii", "abc", &variants));
+        assert!(!verify("unicode", "äbc", &variants));
+    }
+}
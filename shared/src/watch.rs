@@ -0,0 +1,153 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::batch::parse_answers;
+
+/// How often the watched files are checked for changes.
+const POLL: Duration = Duration::from_millis(500);
+
+/// Where the watched runs build, inside the day's crate directory. Building
+/// into the usual `target/debug` would replace the binary doing the
+/// watching.
+const TARGET_DIR: &str = "target/watch";
+
+// every file in the day's sources and examples, with when it last changed
+fn snapshot(dir: &Path) -> BTreeMap<PathBuf, SystemTime> {
+    let mut files = BTreeMap::new();
+    let mut pending = vec![
+        dir.join("Cargo.toml"),
+        dir.join("src"),
+        dir.join("examples"),
+    ];
+
+    while let Some(path) = pending.pop() {
+        if path.is_dir() {
+            if let Ok(entries) = fs::read_dir(&path) {
+                pending.extend(entries.filter_map(|entry| Some(entry.ok()?.path())));
+            }
+        } else if let Ok(modified) = fs::metadata(&path).and_then(|m| m.modified()) {
+            files.insert(path, modified);
+        }
+    }
+
+    files
+}
+
+// the answers of this run, each marked with how it changed since `previous`
+fn compare(previous: &[(String, String)], current: &[(String, String)]) -> Vec<String> {
+    current
+        .iter()
+        .map(
+            |(label, answer)| match previous.iter().find(|(l, _)| l == label) {
+                Some((_, old)) if old == answer => format!("{label}: {answer}"),
+                Some((_, old)) => format!("{label}: {answer} (was {old})"),
+                None if previous.is_empty() => format!("{label}: {answer}"),
+                None => format!("{label}: {answer} (new)"),
+            },
+        )
+        .collect()
+}
+
+fn cargo(dir: &Path, command: &str) -> Command {
+    let mut cargo = Command::new(std::env::var_os("CARGO").unwrap_or("cargo".into()));
+    cargo
+        .current_dir(dir)
+        .args([command, "-q", "--target-dir", TARGET_DIR]);
+    cargo
+}
+
+// runs the examples and, if they pass, the real input; returns its answers
+fn run_once(dir: &Path, previous: &[(String, String)]) -> Option<Vec<(String, String)>> {
+    println!("examples:");
+    let tests = cargo(dir, "test")
+        .status()
+        .unwrap_or_else(|e| panic!("could not run cargo: {e}"));
+    if !tests.success() {
+        println!("examples failed, not running the real input");
+        return None;
+    }
+
+    println!("real input:");
+    let run = cargo(dir, "run")
+        .output()
+        .unwrap_or_else(|e| panic!("could not run cargo: {e}"));
+    if !run.status.success() {
+        print!("{}", String::from_utf8_lossy(&run.stderr));
+        return None;
+    }
+
+    let answers = parse_answers(&String::from_utf8_lossy(&run.stdout));
+    for line in compare(previous, &answers) {
+        println!("  {line}");
+    }
+    Some(answers)
+}
+
+/// Runs the day's examples and then its real input every time a file in
+/// the `src` or `examples` directory of `dir`, or its `Cargo.toml`,
+/// changes. Every answer is printed next to the one from the run before,
+/// if it differs. Runs until interrupted.
+pub fn run(dir: &Path) -> ! {
+    let mut seen = BTreeMap::new();
+    let mut previous = vec![];
+
+    loop {
+        let files = snapshot(dir);
+        if files != seen {
+            seen = files;
+            if let Some(answers) = run_once(dir, &previous) {
+                previous = answers;
+            }
+            println!("watching {} for changes", dir.display());
+        }
+        thread::sleep(POLL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(answers: &[(&str, &str)]) -> Vec<(String, String)> {
+        answers
+            .iter()
+            .map(|(label, answer)| (label.to_string(), answer.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn compare_works() {
+        let previous = answers(&[("task 1", "142")]);
+        let current = answers(&[("task 1", "141"), ("task 2", "281")]);
+
+        assert_eq!(
+            compare(&previous, &current),
+            vec!["task 1: 141 (was 142)", "task 2: 281 (new)"]
+        );
+        assert_eq!(compare(&[], &previous), vec!["task 1: 142"]);
+        assert_eq!(compare(&previous, &previous), vec!["task 1: 142"]);
+    }
+
+    #[test]
+    fn snapshot_works() {
+        let dir = std::env::temp_dir().join(format!("shared-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join("src/data.txt"), "1").unwrap();
+        fs::write(dir.join("target/day"), "").unwrap();
+
+        let files = snapshot(&dir);
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            vec![&dir.join("src/data.txt")]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}